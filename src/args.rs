@@ -0,0 +1,128 @@
+use std::{collections::VecDeque, str::FromStr};
+
+use anyhow::{Context, Result};
+
+pub struct Args {
+    args: VecDeque<String>,
+}
+
+impl Args {
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Args {
+            args: args.into_iter().collect(),
+        }
+    }
+
+    pub fn peek(&self) -> Option<&str> {
+        self.args.front().map(|s| s.as_str())
+    }
+
+    pub fn next_parsed<T>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match self.next() {
+            None => Ok(None),
+            Some(s) => s
+                .parse()
+                .map(Some)
+                .with_context(|| format!("argument '{name}' is not valid: '{s}'")),
+        }
+    }
+
+    pub fn year(&mut self, default: usize) -> Result<usize> {
+        match self.peek() {
+            Some(arg) if arg.starts_with('y') => {
+                let arg = self.next().unwrap();
+                arg[1..].parse().context("argument 'year' is not a number")
+            }
+            _ => Ok(default),
+        }
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        let long = format!("--{name}");
+
+        match self.args.iter().position(|arg| *arg == long) {
+            Some(i) => {
+                self.args.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // counts short flags like `-v` and `-vv`
+    pub fn count(&mut self, short: char) -> usize {
+        let mut count = 0;
+
+        self.args.retain(|arg| {
+            let repeated = arg
+                .strip_prefix('-')
+                .filter(|rest| !rest.is_empty() && rest.chars().all(|ch| ch == short));
+
+            match repeated {
+                Some(rest) => {
+                    count += rest.len();
+                    false
+                }
+                None => true,
+            }
+        });
+
+        count
+    }
+
+    pub fn value(&mut self, name: &str) -> Result<Option<String>> {
+        Ok(self.values(name)?.pop())
+    }
+
+    pub fn values(&mut self, name: &str) -> Result<Vec<String>> {
+        let long = format!("--{name}");
+        let prefix = format!("--{name}=");
+
+        let mut values = Vec::new();
+        let mut i = 0;
+
+        while i < self.args.len() {
+            if self.args[i] == long {
+                self.args.remove(i);
+                let value = self
+                    .args
+                    .remove(i)
+                    .with_context(|| format!("option '{long}' requires a value"))?;
+                values.push(value);
+            } else if let Some(value) = self.args[i].strip_prefix(&prefix) {
+                values.push(value.to_string());
+                self.args.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        Ok(values)
+    }
+
+    pub fn value_parsed<T>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match self.value(name)? {
+            None => Ok(None),
+            Some(s) => s
+                .parse()
+                .map(Some)
+                .with_context(|| format!("option '--{name}' is not valid: '{s}'")),
+        }
+    }
+}
+
+impl Iterator for Args {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.args.pop_front()
+    }
+}
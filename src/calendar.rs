@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::{
+    client::Client,
+    input::{Source, cached, input_path, load_answers},
+    runner::{Answer, Day},
+    stats::{self, Times},
+};
+
+// stars change with every submission, so the page is only reused briefly
+const MAX_AGE: Duration = Duration::from_secs(60);
+
+// stars of each unlocked day on the event page, starting with day 1
+pub fn fetch_stars(year: usize, user: Option<&str>) -> Result<Vec<u8>> {
+    let name = format!("calendar-{year}-{}.html", user.unwrap_or("default"));
+    let fetch = || Client::new(user)?.get(&format!("/{year}"));
+
+    // logged out visitors get the same calendar without any stars
+    cached(&name, MAX_AGE, fetch, |page| match page.contains("/auth/logout") {
+        true => Ok(parse_stars(page)),
+        false => anyhow::bail!("calendar page is not logged in, is the session expired?"),
+    })
+}
+
+// days link to their puzzle with labels like `aria-label="Day 3, two stars"`
+fn parse_stars(page: &str) -> Vec<u8> {
+    let mut stars = Vec::new();
+
+    for label in page.split("aria-label=\"Day ").skip(1) {
+        let label = label.split('"').next().unwrap_or_default();
+        let (day, rest) = label.split_once(',').unwrap_or((label, ""));
+
+        let Ok(day) = day.trim().parse::<usize>()
+        else { continue };
+
+        if stars.len() < day {
+            stars.resize(day, 0);
+        }
+
+        stars[day - 1] = match rest.trim() {
+            "two stars" => 2,
+            "one star" => 1,
+            _ => 0,
+        };
+    }
+
+    stars
+}
+
+struct Row {
+    day: usize,
+    site: Option<u8>,
+    registered: bool,
+    input: bool,
+    answers: usize,
+    unsubmitted: usize,
+}
+
+// parts with an answer recorded locally but no submit time, unless the site's
+// stars show they were solved anyway, e.g. before times were recorded
+fn unsubmitted(answers: &[Option<Answer>; 2], times: &Times, site: Option<u8>) -> usize {
+    (1..=2)
+        .filter(|&part| answers[part - 1].is_some() && times.solved[part - 1].is_none())
+        .filter(|&part| site.is_none_or(|site| (site as usize) < part))
+        .count()
+}
+
+impl Row {
+    fn gaps(&self) -> Vec<&'static str> {
+        let mut gaps = Vec::new();
+        let site = self.site.unwrap_or(0) as usize;
+
+        if site > 0 && !self.registered {
+            gaps.push("solved on site but no code registered");
+        }
+
+        if self.registered && self.unsubmitted > 0 {
+            gaps.push("code exists but answer never submitted");
+        }
+
+        if site > self.answers {
+            gaps.push("answers not recorded locally");
+        }
+
+        if self.registered && !self.input {
+            gaps.push("input not cached");
+        }
+
+        gaps
+    }
+}
+
+pub fn print(year: usize, days: &[Day], user: Option<&str>) -> Result<()> {
+    let stars = match fetch_stars(year, user) {
+        Ok(stars) => Some(stars),
+        Err(e) => {
+            log::warn!("could not fetch star progress, only showing local state: {e}");
+            None
+        }
+    };
+
+    let source = Source::new(user.map(str::to_string), "");
+
+    let registered: Vec<usize> = days.iter().filter(|d| d.year == year).map(|d| d.day).collect();
+
+    let last = stars
+        .as_ref()
+        .map_or(0, Vec::len)
+        .max(registered.iter().copied().max().unwrap_or(0));
+
+    println!("{:>4}  {:<4}  {:<4}  {:<5}  {:<7}  Gaps", "Day", "Site", "Code", "Input", "Answers");
+
+    let mut total = 0;
+
+    for day in 1..=last {
+        let answers = load_answers(year, day, &source)?;
+        let times = stats::load_times(year, day, user)?;
+        let site = stars.as_ref().map(|stars| stars.get(day - 1).copied().unwrap_or(0));
+
+        let row = Row {
+            day,
+            site,
+            registered: registered.contains(&day),
+            input: input_path(year, day, &source).exists(),
+            answers: answers.iter().flatten().count(),
+            unsubmitted: unsubmitted(&answers, &times, site),
+        };
+
+        let site = match row.site {
+            None => "?".to_string(),
+            Some(0) => "·".to_string(),
+            Some(n) => "★".repeat(n as usize),
+        };
+
+        let yes_no = |b: bool| if b { "yes" } else { "-" };
+
+        let line = format!(
+            "{:>4}  {site:<4}  {:<4}  {:<5}  {:<7}  {}",
+            row.day,
+            yes_no(row.registered),
+            yes_no(row.input),
+            row.answers,
+            row.gaps().join(", "),
+        );
+        println!("{}", line.trim_end());
+
+        total += row.site.unwrap_or(0) as usize;
+    }
+
+    if stars.is_some() {
+        println!("{total} stars on the site");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(site: Option<u8>, answers: usize, unsubmitted: usize) -> Row {
+        Row { day: 1, site, registered: true, input: true, answers, unsubmitted }
+    }
+
+    #[test]
+    fn unsubmitted_parts() {
+        let answers = [Some(Answer::from("1")), Some(Answer::from("2"))];
+        let times = Times { fetched: Some(10), solved: [Some(20), None] };
+
+        assert_eq!(unsubmitted(&answers, &times, None), 1);
+        assert_eq!(unsubmitted(&answers, &times, Some(1)), 1);
+        assert_eq!(unsubmitted(&answers, &times, Some(2)), 0);
+        assert_eq!(unsubmitted(&answers, &Times::default(), Some(1)), 1);
+        assert_eq!(unsubmitted(&[None, None], &Times::default(), None), 0);
+    }
+
+    #[test]
+    fn gaps() {
+        let never_submitted = "code exists but answer never submitted";
+
+        assert!(row(Some(1), 2, 1).gaps().contains(&never_submitted));
+        assert!(row(None, 1, 1).gaps().contains(&never_submitted));
+        assert!(row(Some(2), 2, 0).gaps().is_empty());
+        assert_eq!(row(Some(2), 1, 0).gaps(), ["answers not recorded locally"]);
+    }
+}
@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+// requests to the puzzle site, authenticated as one account
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+// `AOC_SESSION` for the default account, `AOC_SESSION_ALICE` for user `alice`
+pub fn session_var(user: Option<&str>) -> String {
+    match user {
+        None => "AOC_SESSION".to_string(),
+        Some(user) => {
+            let name: String = user
+                .chars()
+                .map(|ch| if ch.is_ascii_alphanumeric() { ch.to_ascii_uppercase() } else { '_' })
+                .collect();
+            format!("AOC_SESSION_{name}")
+        }
+    }
+}
+
+impl Client {
+    // `AOC_BASE_URL` points the client at another server, like `mock-server`
+    pub fn new(user: Option<&str>) -> Result<Self> {
+        let base_url = std::env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.to_string());
+        Self::with_base_url(base_url, user)
+    }
+
+    pub fn with_base_url(base_url: impl Into<String>, user: Option<&str>) -> Result<Self> {
+        let var = session_var(user);
+        let session =
+            std::env::var(&var).with_context(|| format!("{var} environment variable not found"))?;
+
+        Ok(Self::with_session(base_url, session))
+    }
+
+    pub fn with_session(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String> {
+        let resp = ureq::get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        response(path, resp)
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String> {
+        let resp = ureq::post(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(fields);
+
+        response(path, resp)
+    }
+
+    pub fn submit(&self, year: usize, day: usize, part: usize, answer: &str) -> Result<Verdict> {
+        let body = self.post_form(
+            &format!("/{year}/day/{day}/answer"),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+
+        Ok(Verdict::classify(&body))
+    }
+}
+
+// error statuses come with a message from the site, like an expired session
+fn response(path: &str, resp: Result<ureq::Response, ureq::Error>) -> Result<String> {
+    match resp {
+        Ok(resp) => Ok(resp.into_string()?),
+        Err(ureq::Error::Status(code, resp)) => {
+            let body = resp.into_string().unwrap_or_default();
+            let message = body.lines().next().unwrap_or_default().trim();
+            anyhow::bail!("request to '{path}' failed with status {code}: {message}")
+        }
+        Err(e) => Err(e).with_context(|| format!("request to '{path}' failed")),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    // with the server's hint like "your answer is too high"
+    Wrong(Option<String>),
+    // with how long the server wants us to wait
+    TooRecent(Option<String>),
+    // the part is locked or was already solved
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn classify(body: &str) -> Self {
+        let text = article_text(body);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|hint| text.contains(hint))
+                .map(|hint| format!("your answer is {hint}"));
+            Verdict::Wrong(hint)
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Verdict::TooRecent(wait)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+}
+
+// the message of an answer page, without markup
+fn article_text(body: &str) -> String {
+    // what follows `<article` is still inside its opening tag
+    let (article, mut in_tag) = match body
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+    {
+        Some((article, _)) => (article, true),
+        None => (body, false),
+    };
+
+    let mut text = String::new();
+
+    for ch in article.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            ch if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,447 @@
+use std::{
+    collections::HashMap,
+    io::{IsTerminal, Read, Write},
+    panic::AssertUnwindSafe,
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    calendar,
+    client::{Client, Verdict},
+    input::{Source, load_answers, load_input_file, record_answer},
+    puzzle,
+    runner::{self, Answer, CpuClock, Day, PartRun, Status},
+    stats::{self, Event},
+    util::terminal::{self, BOLD, DIM, GREEN, RED, RESET, REVERSE, RawMode, YELLOW},
+};
+
+const BENCH_ITERATIONS: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DayStatus {
+    NoCode,
+    // both answers are recorded but the day wasn't run yet
+    NotRun,
+    Unsolved,
+    Matches,
+    Mismatch,
+    Failing,
+}
+
+impl DayStatus {
+    fn label(self) -> String {
+        match self {
+            DayStatus::NoCode => format!("{DIM}no code{RESET}"),
+            DayStatus::NotRun => "not run".to_string(),
+            DayStatus::Unsolved => format!("{YELLOW}unsolved{RESET}"),
+            DayStatus::Matches => format!("{GREEN}matches{RESET}"),
+            DayStatus::Mismatch => format!("{RED}mismatch{RESET}"),
+            DayStatus::Failing => format!("{RED}failing{RESET}"),
+        }
+    }
+}
+
+enum Key {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Escape,
+    Char(char),
+}
+
+// the last run of a day's parts, or why it couldn't run at all
+type DayRun = Result<Vec<PartRun>, String>;
+
+pub struct Dashboard<'a> {
+    year: usize,
+    days: Vec<&'a Day>,
+    source: Source,
+    // stars on the site, `None` when they couldn't be fetched
+    site_stars: Option<Vec<u8>>,
+    runs: HashMap<usize, DayRun>,
+    selected: usize,
+    message: String,
+}
+
+impl<'a> Dashboard<'a> {
+    pub fn new(year: usize, days: &'a [Day], source: Source) -> Self {
+        Dashboard {
+            year,
+            days: days.iter().filter(|d| d.year == year).collect(),
+            source,
+            site_stars: None,
+            runs: HashMap::new(),
+            selected: 0,
+            message: String::new(),
+        }
+    }
+
+    pub fn run(mut self) -> Result<()> {
+        if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+            anyhow::bail!("the dashboard needs a terminal");
+        }
+
+        self.site_stars = match calendar::fetch_stars(self.year, self.source.user.as_deref()) {
+            Ok(stars) => Some(stars),
+            Err(e) => {
+                self.message = format!("could not fetch stars, only showing local state: {e}");
+                None
+            }
+        };
+
+        let _raw = RawMode::enable()?;
+
+        loop {
+            self.draw()?;
+
+            match read_key()? {
+                Key::Char('q' | '\x03') | Key::Escape => return Ok(()),
+                Key::Up | Key::Char('k') => self.selected = self.selected.saturating_sub(1),
+                Key::Down | Key::Char('j') => self.selected = (self.selected + 1).min(24),
+                Key::PageUp => self.selected = 0,
+                Key::PageDown => self.selected = 24,
+                Key::Char('r') => self.run_day(1),
+                Key::Char('b') => self.run_day(BENCH_ITERATIONS),
+                Key::Char('p') => self.show_puzzle()?,
+                Key::Char('s') => self.submit(),
+                _ => {}
+            }
+        }
+    }
+
+    fn day(&self) -> usize {
+        self.selected + 1
+    }
+
+    fn registered(&self, day: usize) -> Option<&'a Day> {
+        self.days.iter().find(|d| d.day == day).copied()
+    }
+
+    fn status(&self, day: usize, recorded: &[Option<Answer>; 2]) -> DayStatus {
+        if self.registered(day).is_none() {
+            return DayStatus::NoCode;
+        }
+
+        let statuses: Vec<Status> = match self.runs.get(&day) {
+            Some(Err(_)) => return DayStatus::Failing,
+            Some(Ok(runs)) => runs.iter().map(PartRun::status).collect(),
+            None => {
+                return match recorded.iter().all(Option::is_some) {
+                    true => DayStatus::NotRun,
+                    false => DayStatus::Unsolved,
+                };
+            }
+        };
+
+        if statuses.contains(&Status::Failed) {
+            DayStatus::Failing
+        } else if statuses.contains(&Status::Wrong) {
+            DayStatus::Mismatch
+        } else if statuses.iter().all(|&s| s == Status::Correct) {
+            DayStatus::Matches
+        } else {
+            DayStatus::Unsolved
+        }
+    }
+
+    // the site's count once fetched, though answers recorded since then count too
+    fn stars(&self, day: usize, recorded: &[Option<Answer>; 2]) -> usize {
+        let local = recorded.iter().flatten().count();
+        let site = self.site_stars.as_ref().and_then(|s| s.get(day - 1)).copied().unwrap_or(0);
+
+        local.max(site as usize)
+    }
+
+    fn draw(&self) -> Result<()> {
+        let mut lines = Vec::new();
+
+        let account = match &self.source.user {
+            Some(user) => format!(" [{user}]"),
+            None => String::new(),
+        };
+
+        // read once per draw, every column below is based on them
+        let recorded = (1..=25)
+            .map(|day| load_answers(self.year, day, &self.source))
+            .collect::<Result<Vec<_>>>()?;
+
+        let total: usize = (1..=25).map(|day| self.stars(day, &recorded[day - 1])).sum();
+
+        lines.push(format!("{BOLD}y{}{account}{RESET}  {total} stars", self.year));
+        lines.push(String::new());
+        lines.push(format!(
+            "{BOLD}{:>4}  {:<5}  {:<8}  {:<20}  {:<20}  {:>10}{RESET}",
+            "Day", "Stars", "Status", "Part 1", "Part 2", "Time",
+        ));
+
+        for day in 1..=25 {
+            let recorded = &recorded[day - 1];
+
+            let stars = self.stars(day, recorded);
+            let stars = "★".repeat(stars) + &"·".repeat(2 - stars);
+
+            let status = self.status(day, recorded);
+
+            let (answers, time) = match self.runs.get(&day) {
+                Some(Ok(runs)) => {
+                    let answers = [1, 2].map(|part| {
+                        match runs.iter().find(|run| run.part == part).map(|run| &run.answer) {
+                            Some(Ok(answer)) => answer.to_string(),
+                            Some(Err(e)) => e.lines().next().unwrap_or_default().to_string(),
+                            None => String::new(),
+                        }
+                    });
+                    let time = runs.iter().map(PartRun::median).sum();
+
+                    (answers, runner::format_duration(time))
+                }
+                Some(Err(e)) => (
+                    [e.lines().next().unwrap_or_default().to_string(), String::new()],
+                    String::new(),
+                ),
+                // recorded answers until the day is run
+                None => (
+                    recorded.clone().map(|answer| answer.map(|a| format!("{DIM}{a}{RESET}")).unwrap_or_default()),
+                    String::new(),
+                ),
+            };
+
+            // the status and answers carry escapes, which don't count towards the width
+            let line = format!(
+                "{day:>4}  {stars:<5}  {}  {}  {}  {time:>10}",
+                pad(&status.label(), 8),
+                pad(&answers[0], 20),
+                pad(&answers[1], 20),
+            );
+
+            lines.push(match day == self.day() {
+                true => format!("{REVERSE}{}{RESET}", line.replace(RESET, &format!("{RESET}{REVERSE}"))),
+                false => line,
+            });
+        }
+
+        lines.push(String::new());
+        lines.push(self.message.clone());
+        lines.push(format!(
+            "{DIM}↑/↓ select  r run  b bench ({BENCH_ITERATIONS} runs)  p puzzle  s submit  q quit{RESET}"
+        ));
+
+        write_screen(&lines)
+    }
+
+    fn run_day(&mut self, iterations: usize) {
+        let day = self.day();
+
+        let Some(registered) = self.registered(day)
+        else {
+            self.message = format!("day {day} has no code");
+            return;
+        };
+
+        // shown below the table while the parts run, next to their progress bars
+        print!("\x1b[{};1H\x1b[Krunning day {day}...\r\n", 25 + 5);
+        std::io::stdout().flush().ok();
+
+        let run = self.run_parts(registered, iterations);
+
+        self.message = match &run {
+            Ok(runs) => format!(
+                "ran day {day}, {} of {} parts match",
+                runs.iter().filter(|run| run.status() == Status::Correct).count(),
+                runs.len(),
+            ),
+            Err(e) => format!("day {day} failed: {}", e.lines().next().unwrap_or_default()),
+        };
+
+        self.runs.insert(day, run);
+    }
+
+    fn run_parts(&self, day: &Day, iterations: usize) -> DayRun {
+        let input = load_input_file(day.year, day.day, &self.source).map_err(|e| format!("{e:#}"))?;
+
+        // a panicking part is reported as failing instead of ending the dashboard
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            [1, 2]
+                .into_iter()
+                .map(|part| {
+                    let variant = day.part(part)?;
+                    runner::run_part(day, variant, &self.source, input.clone(), iterations, CpuClock::Process)
+                })
+                .collect::<Result<Vec<_>>>()
+        }));
+
+        std::panic::set_hook(hook);
+
+        match result {
+            Ok(runs) => runs.map_err(|e| format!("{e:#}")),
+            Err(panic) => Err(match panic.downcast_ref::<String>() {
+                Some(message) => format!("panicked: {message}"),
+                None => match panic.downcast_ref::<&str>() {
+                    Some(message) => format!("panicked: {message}"),
+                    None => "panicked".to_string(),
+                },
+            }),
+        }
+    }
+
+    // submits the last run's answer of the first part without a recorded answer
+    fn submit(&mut self) {
+        self.message = match self.try_submit() {
+            Ok(message) => message,
+            Err(e) => format!("{e:#}"),
+        };
+    }
+
+    fn try_submit(&mut self) -> Result<String> {
+        let day = self.day();
+        let answers = load_answers(self.year, day, &self.source)?;
+
+        let Some(part) = (1..=2).find(|part| answers[part - 1].is_none())
+        else { return Ok(format!("day {day} is already solved")) };
+
+        let Some(Ok(runs)) = self.runs.get(&day)
+        else { anyhow::bail!("run day {day} before submitting") };
+
+        let Some(run) = runs.iter().find(|run| run.part == part)
+        else { anyhow::bail!("part {part} has no answer to submit") };
+
+        // an answer under overridden parameters isn't the puzzle's answer
+        if !run.params.is_empty() {
+            let names: Vec<&str> = run.params.iter().map(|(name, _)| *name).collect();
+            anyhow::bail!("not submitting an answer computed with overridden parameters: {}", names.join(", "));
+        }
+
+        let answer = match &run.answer {
+            Ok(answer) => answer.clone(),
+            Err(_) => anyhow::bail!("part {part} has no answer to submit"),
+        };
+
+        let user = self.source.user.as_deref();
+
+        Ok(match Client::new(user)?.submit(self.year, day, part, answer.as_str())? {
+            Verdict::Correct => {
+                stats::record(self.year, day, user, Event::Solved(part))?;
+                record_answer(self.year, day, &self.source, part, &answer)?;
+
+                // the run is now compared with the answer that was just recorded
+                if let Some(Ok(runs)) = self.runs.get_mut(&day) {
+                    for run in runs.iter_mut().filter(|run| run.part == part && run.params.is_empty()) {
+                        run.expected = Some(answer.clone());
+                    }
+                }
+
+                format!("{answer} is correct for day {day} part {part}")
+            }
+            Verdict::Wrong(hint) => match hint {
+                Some(hint) => format!("{answer} is wrong, {hint}"),
+                None => format!("{answer} is wrong"),
+            },
+            Verdict::TooRecent(wait) => match wait {
+                Some(wait) => format!("answered too recently, wait {wait}"),
+                None => "answered too recently".to_string(),
+            },
+            Verdict::WrongLevel => format!("part {part} is locked or was already solved"),
+            Verdict::Unknown(text) => format!("unexpected response: {text}"),
+        })
+    }
+
+    fn show_puzzle(&mut self) -> Result<()> {
+        let day = self.day();
+
+        let markdown = match puzzle::load_description(self.year, day, &self.source, false) {
+            Ok(markdown) => markdown,
+            Err(e) => {
+                self.message = format!("could not load the puzzle: {e:#}");
+                return Ok(());
+            }
+        };
+
+        let lines = puzzle::render(&markdown);
+        let height = terminal::height().saturating_sub(1).max(1);
+        let last = lines.len().saturating_sub(height);
+        let mut top = 0;
+
+        loop {
+            let mut screen: Vec<String> = lines.iter().skip(top).take(height).cloned().collect();
+            screen.resize(height, String::new());
+            screen.push(format!("{DIM}day {day}  ↑/↓ scroll  space page  q back{RESET}"));
+
+            write_screen(&screen)?;
+
+            match read_key()? {
+                Key::Char('q' | 'p' | '\x03') | Key::Escape => return Ok(()),
+                Key::Up | Key::Char('k') => top = top.saturating_sub(1),
+                Key::Down | Key::Char('j') => top = (top + 1).min(last),
+                Key::PageUp => top = top.saturating_sub(height),
+                Key::PageDown | Key::Char(' ') => top = (top + height).min(last),
+                _ => {}
+            }
+        }
+    }
+}
+
+// pads to a visible width, not counting escape sequences
+fn pad(text: &str, width: usize) -> String {
+    let mut visible = 0;
+    let mut escape = false;
+    let mut out = String::new();
+
+    for ch in text.chars() {
+        match ch {
+            '\x1b' => escape = true,
+            'm' if escape => escape = false,
+            _ if !escape => {
+                if visible == width {
+                    continue;
+                }
+                visible += 1;
+            }
+            _ => {}
+        }
+
+        out.push(ch);
+    }
+
+    out + &" ".repeat(width - visible)
+}
+
+fn write_screen(lines: &[String]) -> Result<()> {
+    let mut out = String::from("\x1b[H");
+
+    for line in lines {
+        out.push_str(line);
+        out.push_str("\x1b[K\r\n");
+    }
+
+    out.push_str("\x1b[J");
+
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(out.as_bytes())?;
+    stdout.flush().context("could not draw the dashboard")
+}
+
+fn read_key() -> Result<Key> {
+    let mut buf = [0; 8];
+
+    loop {
+        let n = std::io::stdin().read(&mut buf).context("could not read from the terminal")?;
+
+        let key = match &buf[..n] {
+            [] => anyhow::bail!("the terminal was closed"),
+            b"\x1b" => Key::Escape,
+            b"\x1b[A" | b"\x1bOA" => Key::Up,
+            b"\x1b[B" | b"\x1bOB" => Key::Down,
+            b"\x1b[5~" => Key::PageUp,
+            b"\x1b[6~" => Key::PageDown,
+            [byte] if byte.is_ascii() => Key::Char(*byte as char),
+            _ => continue,
+        };
+
+        return Ok(key);
+    }
+}
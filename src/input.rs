@@ -0,0 +1,270 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    client::Client,
+    runner::Answer,
+    stats::{self, Event},
+    vault,
+};
+
+// which copy of a day's input to use, named users keep theirs under
+// `input/{user}/{year}` while the default account uses `input/{year}`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Source {
+    pub user: Option<String>,
+    pub suffix: String,
+}
+
+impl Source {
+    pub fn new(user: Option<String>, suffix: impl Into<String>) -> Self {
+        Source { user, suffix: suffix.into() }
+    }
+
+    pub fn label(&self) -> String {
+        match (&self.user, self.suffix.as_str()) {
+            (None, suffix) => suffix.to_string(),
+            (Some(user), "") => user.clone(),
+            (Some(user), suffix) => format!("{user}/{suffix}"),
+        }
+    }
+
+    pub fn dir(&self, year: usize) -> PathBuf {
+        match &self.user {
+            None => PathBuf::from(format!("input/{year}")),
+            Some(user) => PathBuf::from(format!("input/{user}/{year}")),
+        }
+    }
+}
+
+pub fn input_path(year: usize, day: usize, source: &Source) -> PathBuf {
+    source.dir(year).join(format!("{day}{}", source.suffix))
+}
+
+pub fn load_input_file(year: usize, day: usize, source: &Source) -> Result<String> {
+    let path = input_path(year, day, source);
+
+    match vault::read(&path) {
+        Ok(input) => Ok(input),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && source.suffix.is_empty() => {
+            eprintln!("file '{}' not found, downloading...", path.display());
+
+            let input = download_input_file(year, day, source.user.as_deref())
+                .context("error downloading input file")?;
+
+            if let Err(e) = stats::record(year, day, source.user.as_deref(), Event::Fetched) {
+                eprintln!("could not record fetch time:\n{e:?}");
+            }
+
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).ok();
+            }
+
+            match vault::write(&path, &input) {
+                Ok(()) => {}
+                Err(e) => {
+                    eprintln!("could not write input to file '{}':\n{e:?}", path.display());
+                }
+            }
+
+            Ok(input)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("file '{}' not found", path.display()))
+        }
+        Err(e) => Err(e).with_context(|| format!("could not read '{}'", path.display())),
+    }
+}
+
+pub fn download_input_file(year: usize, day: usize, user: Option<&str>) -> Result<String> {
+    Client::new(user)?.get(&format!("/{year}/day/{day}/input"))
+}
+
+// answers are stored next to their input as lines of `<part>: <answer>`
+pub fn answers_path(year: usize, day: usize, source: &Source) -> PathBuf {
+    source.dir(year).join(format!("{day}{}.answers", source.suffix))
+}
+
+pub fn load_answers(year: usize, day: usize, source: &Source) -> Result<[Option<Answer>; 2]> {
+    let path = answers_path(year, day, source);
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok([None, None]),
+        Err(e) => {
+            return Err(e).with_context(|| format!("could not read answers '{}'", path.display()));
+        }
+    };
+
+    let mut answers = [None, None];
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (part, answer) = line
+            .split_once(':')
+            .with_context(|| format!("{}:{}: expected '<part>: <answer>'", path.display(), i + 1))?;
+
+        let part: usize = part
+            .trim()
+            .parse()
+            .with_context(|| format!("{}:{}: part is not a number", path.display(), i + 1))?;
+
+        let slot = answers
+            .get_mut(part.wrapping_sub(1))
+            .with_context(|| format!("{}:{}: '{part}' is not a valid part", path.display(), i + 1))?;
+
+        *slot = Some(Answer::from(answer.trim()));
+    }
+
+    Ok(answers)
+}
+
+// replaces any answer recorded for the part
+pub fn record_answer(year: usize, day: usize, source: &Source, part: usize, answer: &Answer) -> Result<()> {
+    let path = answers_path(year, day, source);
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("could not read answers '{}'", path.display()));
+        }
+    };
+
+    let mut lines: Vec<String> = text
+        .lines()
+        .filter(|line| line.split_once(':').is_none_or(|(p, _)| p.trim() != part.to_string()))
+        .map(str::to_string)
+        .collect();
+
+    lines.push(format!("{part}: {answer}"));
+    lines.sort();
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+
+    std::fs::write(&path, lines.join("\n") + "\n")
+        .with_context(|| format!("could not write answers '{}'", path.display()))
+}
+
+// named users are the directories in `input`, apart from the default
+// account's years and the hidden response cache
+pub fn users() -> Result<Vec<String>> {
+    let entries = match std::fs::read_dir("input") {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).context("could not list 'input'"),
+    };
+
+    let mut users = Vec::new();
+
+    for entry in entries {
+        let entry = entry.context("could not list 'input'")?;
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        let is_year = name.bytes().all(|b| b.is_ascii_digit());
+
+        if entry.file_type()?.is_dir() && !name.starts_with('.') && !is_year {
+            users.push(name);
+        }
+    }
+
+    users.sort();
+    Ok(users)
+}
+
+// the default account's files used to sit directly in `input` when 2024 was
+// the only year, so any left there are moved into `input/2024` once
+pub fn migrate_flat_layout() -> Result<()> {
+    let entries = match std::fs::read_dir("input") {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).context("could not list 'input'"),
+    };
+
+    let dir = Path::new("input/2024");
+    let mut moved = 0;
+
+    for entry in entries {
+        let entry = entry.context("could not list 'input'")?;
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        if !entry.file_type()?.is_file() || !name.starts_with(|ch: char| ch.is_ascii_digit()) {
+            continue;
+        }
+
+        let to = dir.join(&name);
+
+        if to.exists() {
+            eprintln!("not moving '{}', '{}' already exists", entry.path().display(), to.display());
+            continue;
+        }
+
+        std::fs::create_dir_all(dir).with_context(|| format!("could not create '{}'", dir.display()))?;
+        std::fs::rename(entry.path(), &to)
+            .with_context(|| format!("could not move '{}' to '{}'", entry.path().display(), to.display()))?;
+
+        moved += 1;
+    }
+
+    if moved > 0 {
+        eprintln!("moved {moved} files of 2024 from 'input' into '{}'", dir.display());
+    }
+
+    Ok(())
+}
+
+pub fn cache_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("input/.cache/{name}"))
+}
+
+// a response cached for `max_age`, fetched again once it's older; only text
+// that `parse` accepts is written, so an error or login page is never reused
+pub fn cached<T>(
+    name: &str,
+    max_age: Duration,
+    fetch: impl FnOnce() -> Result<String>,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<T> {
+    let path = cache_path(name);
+
+    let age = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+
+    if age.is_some_and(|age| age < max_age) {
+        log::debug!("using cached '{}'", path.display());
+
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("could not read cache '{}'", path.display()))?;
+
+        match parse(&text) {
+            Ok(value) => return Ok(value),
+            Err(e) => log::debug!("fetching again, cache '{}' is not valid: {e}", path.display()),
+        }
+    }
+
+    let text = fetch()?;
+    let value = parse(&text)?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+
+    if let Err(e) = std::fs::write(&path, &text) {
+        eprintln!("could not write cache '{}':\n{e:?}", path.display());
+    }
+
+    Ok(value)
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+// what we'd otherwise check by hand before choosing how to parse an input
+pub struct Summary {
+    pub lines: usize,
+    pub widths: (usize, usize),
+    pub chars: Vec<(char, usize)>,
+    pub integers: Option<Integers>,
+    // line counts of the blank line separated sections
+    pub sections: Vec<usize>,
+}
+
+pub struct Integers {
+    pub count: usize,
+    pub min: i128,
+    pub max: i128,
+    // too big to even fit an i128
+    pub overflowed: bool,
+}
+
+pub fn summarize(input: &str) -> Summary {
+    let lines: Vec<&str> = input.lines().collect();
+
+    let widths = lines.iter().map(|line| line.chars().count());
+    let widths = (widths.clone().min().unwrap_or(0), widths.max().unwrap_or(0));
+
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for ch in input.chars().filter(|ch| *ch != '\n') {
+        *counts.entry(ch).or_default() += 1;
+    }
+
+    let mut chars: Vec<(char, usize)> = counts.into_iter().collect();
+    chars.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut sections = Vec::new();
+    let mut current = 0;
+
+    for line in &lines {
+        match line.trim().is_empty() {
+            true if current > 0 => {
+                sections.push(current);
+                current = 0;
+            }
+            true => {}
+            false => current += 1,
+        }
+    }
+
+    if current > 0 {
+        sections.push(current);
+    }
+
+    Summary {
+        lines: lines.len(),
+        widths,
+        chars,
+        integers: integers(input),
+        sections,
+    }
+}
+
+// a `-` is only a sign when it isn't joining two words, like in `a-b`
+fn integers(input: &str) -> Option<Integers> {
+    let bytes = input.as_bytes();
+
+    let mut found: Option<Integers> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let negative = start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+
+        let digits = &input[start..i];
+        let value = digits.parse::<i128>().ok().map(|n| if negative { -n } else { n });
+
+        let summary = found.get_or_insert(Integers {
+            count: 0,
+            min: i128::MAX,
+            max: i128::MIN,
+            overflowed: false,
+        });
+
+        summary.count += 1;
+
+        match value {
+            Some(n) => {
+                summary.min = summary.min.min(n);
+                summary.max = summary.max.max(n);
+            }
+            None => summary.overflowed = true,
+        }
+    }
+
+    found
+}
+
+impl Integers {
+    fn exceeds(&self, min: i128, max: i128) -> bool {
+        self.overflowed || self.min < min || self.max > max
+    }
+}
+
+pub fn print(summary: &Summary) {
+    let (min_width, max_width) = summary.widths;
+
+    match min_width == max_width {
+        true if summary.lines > 1 => println!(
+            "{} lines, all {max_width} wide (a {max_width}x{} grid candidate)",
+            summary.lines, summary.lines,
+        ),
+        _ => println!("{} lines, {min_width} to {max_width} wide", summary.lines),
+    }
+
+    match summary.sections.len() {
+        0 | 1 => {}
+        n => {
+            let lines: Vec<String> = summary.sections.iter().map(usize::to_string).collect();
+            println!("{n} sections of {} lines", lines.join(", "));
+        }
+    }
+
+    match &summary.integers {
+        None => println!("no integers"),
+        Some(ints) => {
+            match ints.min <= ints.max {
+                true => println!("{} integers from {} to {}", ints.count, ints.min, ints.max),
+                false => println!("{} integers", ints.count),
+            }
+
+            if ints.overflowed {
+                println!("  some don't fit an i128");
+            }
+
+            for (name, min, max) in [
+                ("u32", 0, u32::MAX as i128),
+                ("i64", i64::MIN as i128, i64::MAX as i128),
+                ("usize", 0, usize::MAX as i128),
+            ] {
+                if ints.exceeds(min, max) {
+                    println!("  some don't fit a {name}");
+                }
+            }
+        }
+    }
+
+    println!("{} distinct characters:", summary.chars.len());
+
+    for (ch, count) in &summary.chars {
+        let shown = match ch {
+            ' ' => "' '".to_string(),
+            '\t' => "'\\t'".to_string(),
+            '\r' => "'\\r'".to_string(),
+            ch => ch.to_string(),
+        };
+
+        println!("  {shown:>4} {count:>8}");
+    }
+}
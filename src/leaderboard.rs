@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::{client::Client, input::cached, util::Json};
+
+// the site asks for private leaderboards to be fetched at most every 15 minutes
+const MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+pub struct Member {
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u64,
+    // stars of each day, starting with day 1
+    pub days: Vec<u8>,
+}
+
+pub fn fetch(year: usize, id: &str, user: Option<&str>) -> Result<Vec<Member>> {
+    let fetch = || Client::new(user)?.get(&format!("/{year}/leaderboard/private/view/{id}.json"));
+
+    cached(&format!("leaderboard-{year}-{id}.json"), MAX_AGE, fetch, |text| {
+        let json = Json::parse(text).context("leaderboard is not valid json, is the session expired?")?;
+        parse(&json)
+    })
+}
+
+fn parse(json: &Json) -> Result<Vec<Member>> {
+    let members = json
+        .get("members")
+        .and_then(Json::as_object)
+        .context("leaderboard has no members")?;
+
+    let mut out = Vec::new();
+
+    for (id, member) in members {
+        let name = match member.get("name").and_then(Json::as_str) {
+            Some(name) => name.to_string(),
+            None => format!("(anonymous user #{id})"),
+        };
+
+        let number = |key: &str| member.get(key).and_then(Json::as_u64).unwrap_or(0);
+
+        let mut days = vec![0; 25];
+
+        for (day, levels) in member
+            .get("completion_day_level")
+            .and_then(Json::as_object)
+            .unwrap_or_default()
+        {
+            let day: usize = day.parse().with_context(|| format!("'{day}' is not a day"))?;
+            let stars = levels.as_object().map_or(0, |levels| levels.len());
+
+            if let Some(slot) = days.get_mut(day.wrapping_sub(1)) {
+                *slot = stars as u8;
+            }
+        }
+
+        out.push(Member {
+            name,
+            local_score: number("local_score"),
+            stars: number("stars"),
+            days,
+        });
+    }
+
+    out.sort_by(|a, b| (b.local_score, b.stars).cmp(&(a.local_score, a.stars)).then(a.name.cmp(&b.name)));
+
+    Ok(out)
+}
+
+pub fn print(members: &[Member]) {
+    let days = members
+        .iter()
+        .filter_map(|member| member.days.iter().rposition(|stars| *stars > 0))
+        .max()
+        .map_or(0, |last| last + 1);
+
+    let name_width = members.iter().map(|m| m.name.chars().count()).max().unwrap_or(0).max(4);
+
+    // only the last digit of each day fits above its column
+    let day_header: Vec<String> = (1..=days).map(|day| (day % 10).to_string()).collect();
+    let day_header = day_header.join(" ");
+    let header = format!("{:>4}  {:<name_width$}  {:>5}  {:>5}  {day_header}", "#", "Name", "Score", "Stars");
+    println!("{}", header.trim_end());
+
+    for (rank, member) in members.iter().enumerate() {
+        let stars: Vec<&str> = member.days[..days]
+            .iter()
+            .map(|stars| match stars {
+                2 => "★",
+                1 => "☆",
+                _ => "·",
+            })
+            .collect();
+        let stars = stars.join(" ");
+
+        let line = format!(
+            "{:>4}  {:<name_width$}  {:>5}  {:>5}  {stars}",
+            rank + 1,
+            member.name,
+            member.local_score,
+            member.stars,
+        );
+        println!("{}", line.trim_end());
+    }
+}
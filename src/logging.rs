@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use log::{LevelFilter, Log, Metadata, Record};
+use simple_logger::SimpleLogger;
+
+use crate::{args::Args, runner::Day};
+
+struct Logger {
+    inner: SimpleLogger,
+    level: LevelFilter,
+    // sorted longest first so the most specific target wins
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Logger {
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .find(|(module, _)| {
+                target
+                    .strip_prefix(module.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+pub fn init(args: &mut Args, days: &[Day]) -> Result<()> {
+    let verbose = args.count('v');
+    let quiet = args.count('q');
+
+    let level = match (verbose, quiet) {
+        (0, 0) => LevelFilter::Info,
+        (1, 0) => LevelFilter::Debug,
+        (_, 0) => LevelFilter::Trace,
+        (0, 1) => LevelFilter::Warn,
+        (0, _) => LevelFilter::Error,
+        _ => anyhow::bail!("'-v' and '-q' cannot be combined"),
+    };
+
+    let mut targets = vec![("rustls".to_string(), LevelFilter::Off)];
+
+    for filter in args.values("log")? {
+        for directive in filter.split(',') {
+            let (target, level) = directive
+                .split_once('=')
+                .with_context(|| format!("log filter '{directive}' is not of the form 'target=level'"))?;
+
+            let level: LevelFilter = level
+                .parse()
+                .with_context(|| format!("'{level}' is not a valid log level"))?;
+
+            for module in targets_for(target, days)? {
+                targets.retain(|(m, _)| *m != module);
+                targets.push((module, level));
+            }
+        }
+    }
+
+    targets.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+
+    let max_level = targets
+        .iter()
+        .map(|(_, level)| *level)
+        .fold(level, |a, b| a.max(b));
+
+    let logger = Logger {
+        inner: SimpleLogger::new().with_level(LevelFilter::Trace),
+        level,
+        targets,
+    };
+
+    log::set_boxed_logger(Box::new(logger))?;
+    log::set_max_level(max_level);
+
+    Ok(())
+}
+
+// `day6` and `y2024::day6` refer to day modules, anything else is a module path
+fn targets_for(target: &str, days: &[Day]) -> Result<Vec<String>> {
+    let krate = env!("CARGO_CRATE_NAME");
+
+    if target.starts_with('y') && target[1..].starts_with(|ch: char| ch.is_ascii_digit()) {
+        return Ok(vec![format!("{krate}::{target}")]);
+    }
+
+    let Some(day) = target.strip_prefix("day")
+    else { return Ok(vec![target.to_string()]) };
+
+    let day: usize = day
+        .parse()
+        .with_context(|| format!("'{target}' is not a valid day"))?;
+
+    let mut years: Vec<usize> = days
+        .iter()
+        .filter(|d| d.day == day)
+        .map(|d| d.year)
+        .collect();
+    years.dedup();
+
+    if years.is_empty() {
+        anyhow::bail!("'{day}' is not a valid day");
+    }
+
+    Ok(years
+        .into_iter()
+        .map(|year| format!("{krate}::y{year}::day{day}"))
+        .collect())
+}
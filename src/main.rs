@@ -1,37 +1,73 @@
-#![feature(gen_blocks)]
-
-use std::{path::PathBuf, time::Instant};
+use std::path::Path;
 
 use anyhow::{Context, Result};
-use log::LevelFilter;
-use simple_logger::SimpleLogger;
 
+use crate::{
+    args::Args,
+    client::{Client, Verdict},
+    input::{Source, input_path, load_answers, load_input_file, record_answer},
+    runner::{Answer, CpuClock, Report, Status},
+    stats::Event,
+    util::Rng,
+};
+
+pub mod args;
+pub mod calendar;
+pub mod client;
+pub mod dashboard;
+pub mod input;
+pub mod inspect;
+pub mod leaderboard;
+pub mod logging;
+pub mod mock;
+pub mod puzzle;
+pub mod runner;
+pub mod stats;
 pub mod util;
+pub mod vault;
+pub mod watch;
 
 macro_rules! years {
-    ($($year:literal [ $($day:literal),* $(,)? ]),* $(,)?) => {
+    ($(
+        $year:literal [ $(
+            $day:literal $({ $( $method:ident $(( $($arg:ident),* ))? ),* $(,)? })?
+        ),* $(,)? ]
+    ),* $(,)?) => {
         paste::paste! {
-            $(mod [< y $year >] {
+            $(#[cfg(feature = "y" $year)]
+            mod [< y $year >] {
                 $(pub mod [< day $day >];)*
             })*
-            
-
-            fn call(year: usize, day: usize, part: usize, input: String) -> Result<()> {
-                match year {
-                    $(
-                        $year => match day {
-                            $(
-                                $day => match part {
-                                    1 => self::[< y $year >]::[< day $day >]::part1(input),
-                                    2 => self::[< y $year >]::[< day $day >]::part2(input),
-                                    _ => Err(anyhow::anyhow!("'{part}' is not a valid part")),
-                                }
-                            )*
-                            _ => Err(anyhow::anyhow!("'{day}' is not a valid day")),
-                        }
-                    )*
-                    _ => Err(anyhow::anyhow!("'{year}' is not a valid year")),
-                }
+
+            // every year of the macro, including the ones whose feature is disabled
+            const YEARS: &[usize] = &[$($year),*];
+
+            // only the years compiled into this binary
+            const COMPILED_YEARS: &[usize] = &[$(#[cfg(feature = "y" $year)] $year),*];
+
+            #[allow(unused_mut)]
+            fn days() -> Vec<runner::Day> {
+                let mut days = Vec::new();
+
+                $(
+                    #[cfg(feature = "y" $year)]
+                    days.extend([
+                        $(
+                            runner::Day::new($year, $day, [
+                                self::[< y $year >]::[< day $day >]::part1,
+                                self::[< y $year >]::[< day $day >]::part2,
+                            ])
+                            $($(
+                                .$method($($((
+                                    stringify!($arg),
+                                    self::[< y $year >]::[< day $day >]::$arg,
+                                )),*)?)
+                            )*)?,
+                        )*
+                    ]);
+                )*
+
+                days
             }
         }
     };
@@ -39,7 +75,18 @@ macro_rules! years {
 
 const CUR_YEAR: usize = 2024;
 
-years!(2024 [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+years!(2024 [
+    1, 2, 3,
+    4 { generator(generate), variant(part1_oracle), variant(part2_oracle) },
+    5,
+    6 { uses_rayon, generator(generate), variant(part2_oracle), visualize(visualize) },
+    7 { uses_rayon, generator(generate), variant(part1_fast), variant(part2_fast) },
+    8,
+    9 { generator(generate), variant(part2_oracle) },
+    10 { generator(generate), variant(part1_oracle), variant(part2_oracle) },
+    11 { memo(blink_cache) },
+    12 { generator(generate), variant(part1_oracle), variant(part2_oracle), visualize(visualize) },
+]);
 
 fn main() {
     match run() {
@@ -49,100 +96,576 @@ fn main() {
 }
 
 fn run() -> Result<()> {
-    SimpleLogger::new()
-        .with_module_level("rustls", LevelFilter::Off)
-        .with_level(LevelFilter::Info)
-        .init()?;
+    let mut args = Args::new(std::env::args().skip(1));
+
+    runner::params::set_overrides(&args.values("param")?)?;
+
+    let days = days();
 
-    let mut args = std::env::args().skip(1).peekable();
+    logging::init(&mut args, &days)?;
 
-    let first_arg = args
-        .peek()
-        .context("first argument not provided")?;
+    if let Some(threads) = args.value_parsed::<usize>("threads")? {
+        if threads == 0 {
+            anyhow::bail!("option '--threads' must be at least 1");
+        }
+
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .context("could not configure thread pool")?;
+    }
 
-    let year: usize = match first_arg.starts_with('y') {
-        true => {
-            let first_arg = args.next().unwrap();
+    input::migrate_flat_layout()?;
 
-            first_arg[1..].parse()
-                .context("first argument 'year' is not a number")?
+    let result = match args.peek().context("first argument not provided")? {
+        "run-all" => {
+            args.next();
+            run_all(&days, args)
+        }
+        "bench" => {
+            args.next();
+            bench(&days, args)
+        }
+        "compare" => {
+            args.next();
+            compare(&days, args)
+        }
+        "stress" => {
+            args.next();
+            stress(&days, args)
+        }
+        "verify" => {
+            args.next();
+            verify(&days, args)
+        }
+        "submit" => {
+            args.next();
+            submit(&days, args)
+        }
+        "stats" => {
+            args.next();
+            show_stats(args)
+        }
+        "leaderboard" => {
+            args.next();
+            show_leaderboard(args)
+        }
+        "calendar" => {
+            args.next();
+            show_calendar(&days, args)
+        }
+        "dashboard" => {
+            args.next();
+            show_dashboard(&days, args)
+        }
+        "puzzle" => {
+            args.next();
+            show_puzzle(args)
+        }
+        "watch" => {
+            args.next();
+            watch(&days, args)
+        }
+        "visualize" => {
+            args.next();
+            visualize(&days, args)
         }
-        _ => CUR_YEAR,
+        "inspect" => {
+            args.next();
+            inspect_input(args)
+        }
+        "lock" => {
+            args.next();
+            vault::lock().map(|count| println!("encrypted {count} files"))
+        }
+        "unlock" => {
+            args.next();
+            vault::unlock().map(|count| println!("decrypted {count} files"))
+        }
+        "mock-server" => {
+            args.next();
+            mock_server(&days, args)
+        }
+        _ => run_single(&days, args),
     };
 
+    for name in runner::params::unused_overrides() {
+        log::warn!("parameter '{name}' was not used by any part");
+    }
+
+    result
+}
+
+fn run_single(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let outputs = Outputs::new(&mut args)?;
+    let variant = args.value("variant")?;
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+
     let day: usize = args
-        .next()
-        .context("first argument 'day' not provided")?
-        .parse()
-        .context("first argument 'day' is not a number")?;
+        .next_parsed("day")?
+        .context("first argument 'day' not provided")?;
 
     let part: usize = args
-        .next()
-        .context("second argument 'part' not provided")?
-        .parse()
-        .context("second argument 'part' is not a number")?;
-
-    let input = match args.next() {
-        None => load_input_file(day, "")?,
-        Some(s) => {
-            if &s == "-" {
-                args.next().unwrap_or_default()
-            } else {
-                load_input_file(day, &s)?
+        .next_parsed("part")?
+        .context("second argument 'part' not provided")?;
+
+    let day = runner::find(days, year, day)?;
+
+    let variant = match variant {
+        None => day.part(part)?,
+        Some(name) => day.find_variant(&name)?,
+    };
+
+    if variant.part != part {
+        anyhow::bail!("variant '{}' is not a variant of part {part}", variant.name);
+    }
+
+    let (source, input) = read_input(day.year, day.day, user, &mut args)?;
+
+    let run = runner::run_part(day, variant, &source, input, 1, CpuClock::Process)?;
+
+    match &run.answer {
+        Ok(answer) => println!("{answer}"),
+        Err(e) => anyhow::bail!("{e}"),
+    }
+
+    match run.status() {
+        Status::Correct => println!("answer is correct"),
+        Status::Wrong => println!("answer is wrong, expected {}", run.expected.as_ref().unwrap()),
+        _ => {}
+    }
+
+    println!("ran in {}", runner::format_duration(run.median()));
+
+    for line in Report::phase_lines(&run) {
+        println!("{line}");
+    }
+
+    outputs.write(&Report {
+        runs: vec![run],
+        threads: rayon::current_num_threads(),
+    })
+}
+
+fn compare(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let outputs = Outputs::new(&mut args)?;
+    let iterations = args.value_parsed("iterations")?.unwrap_or(1);
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
+
+    let part: usize = args
+        .next_parsed("part")?
+        .context("argument 'part' not provided")?;
+
+    let day = runner::find(days, year, day)?;
+    let (source, input) = read_input(day.year, day.day, user, &mut args)?;
+
+    let report = runner::compare(day, part, &source, input, iterations)?;
+    outputs.write(&report)?;
+
+    report.print_comparison()
+}
+
+fn stress(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let cases = args.value_parsed("cases")?.unwrap_or(1000);
+    let seed = args.value_parsed("seed")?;
+    let year = args.year(CUR_YEAR)?;
+
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
+
+    let part: usize = args
+        .next_parsed("part")?
+        .context("argument 'part' not provided")?;
+
+    let day = runner::find(days, year, day)?;
+
+    // printed so a failure can be reproduced with `--seed`
+    let seed = seed.unwrap_or_else(|| Rng::from_time().next_u64());
+    println!("stress testing y{year} day {} part {part} with seed {seed}", day.day);
+
+    let Some(failure) = runner::stress::stress(day, part, cases, &mut Rng::new(seed))?
+    else {
+        println!("all variants agree on {cases} inputs");
+        return Ok(());
+    };
+
+    println!("case {} failed, shrunk to:\n{}", failure.case, failure.input.trim_end());
+
+    for (name, result) in &failure.results {
+        match result {
+            Ok(answer) => println!("  {name:<16} {answer}"),
+            Err(e) => println!("  {name:<16} error: {}", e.lines().next().unwrap_or_default()),
+        }
+    }
+
+    let path = input_path(year, day.day, &Source::new(None, "stress"));
+    vault::write(&path, &failure.input)
+        .with_context(|| format!("could not write '{}'", path.display()))?;
+    println!("wrote input to '{}', rerun with `compare {} {part} stress`", path.display(), day.day);
+
+    anyhow::bail!("variants of part {part} disagree")
+}
+
+// submits an answer, or the part's answer on the account's input
+fn submit(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let source = Source::new(args.value("user")?, "");
+    let user = source.user.as_deref();
+    let year = args.year(CUR_YEAR)?;
+
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
+
+    let part: usize = args
+        .next_parsed("part")?
+        .context("argument 'part' not provided")?;
+
+    let day = runner::find(days, year, day)?;
+    let variant = day.part(part)?;
+
+    if let Some(answer) = &load_answers(year, day.day, &source)?[part - 1] {
+        anyhow::bail!("part {part} is already solved, the answer was {answer}");
+    }
+
+    let answer = match args.next() {
+        Some(answer) => Answer::from(answer),
+        None => {
+            let input = load_input_file(year, day.day, &source)?;
+            let run = runner::run_part(day, variant, &source, input, 1, CpuClock::Process)?;
+
+            // an answer under overridden parameters isn't the puzzle's answer
+            if !run.params.is_empty() {
+                let names: Vec<&str> = run.params.iter().map(|(name, _)| *name).collect();
+                anyhow::bail!("not submitting an answer computed with overridden parameters: {}", names.join(", "));
             }
+
+            run.answer.map_err(|e| anyhow::anyhow!(e))?
         }
     };
 
-    let start = Instant::now();
+    println!("submitting {answer} for y{year} day {} part {part}", day.day);
 
-    call(year, day, part, input)?;
+    match Client::new(user)?.submit(year, day.day, part, answer.as_str())? {
+        Verdict::Correct => {
+            // the solve time first, it can't be taken again later
+            stats::record(year, day.day, user, Event::Solved(part))?;
+            record_answer(year, day.day, &source, part, &answer)?;
+            println!("answer is correct");
 
-    let end = Instant::now();
+            if let Some(secs) = stats::load_times(year, day.day, user)?.to_solve(part) {
+                println!("solved in {}", stats::format_elapsed(secs));
+            }
+
+            Ok(())
+        }
+        Verdict::Wrong(hint) => match hint {
+            Some(hint) => anyhow::bail!("answer is wrong, {hint}"),
+            None => anyhow::bail!("answer is wrong"),
+        },
+        Verdict::TooRecent(wait) => match wait {
+            Some(wait) => anyhow::bail!("answered too recently, wait {wait}"),
+            None => anyhow::bail!("answered too recently"),
+        },
+        Verdict::WrongLevel => anyhow::bail!("part {part} is locked or was already solved"),
+        Verdict::Unknown(text) => anyhow::bail!("unexpected response: {text}"),
+    }
+}
+
+fn show_stats(mut args: Args) -> Result<()> {
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+
+    stats::print_stats(year, &(1..=25).collect::<Vec<_>>(), user.as_deref())
+}
 
-    let duration = end - start;
+fn show_leaderboard(mut args: Args) -> Result<()> {
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+    let id = args.next().context("argument 'id' not provided")?;
 
-    if duration.as_secs() < 5 {
-        println!("ran in {:.3} millis", duration.as_micros() as f64 / 1000.0);
-    } else {
-        println!("ran in {:.3} seconds", duration.as_secs_f64());
-    }    
+    let members = leaderboard::fetch(year, &id, user.as_deref())?;
+    leaderboard::print(&members);
 
     Ok(())
 }
 
-fn load_input_file(day: usize, suffix: &str) -> Result<String> {
-    let path = format!("input/{day}{suffix}");
-    let path = PathBuf::from(path);
+fn show_calendar(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
 
-    match std::fs::read_to_string(&path) {
-        Ok(input) => Ok(input),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound && suffix.is_empty() => {
-            eprintln!("file '{}' not found, downloading...", path.display());
+    calendar::print(year, days, user.as_deref())
+}
 
-            let input = download_input_file(day).context("error downloading input file")?;
+fn watch(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
 
-            match std::fs::write(&path, &input) {
-                Ok(()) => {}
-                Err(e) => {
-                    eprintln!("could not write input to file '{}':\n{e:?}", path.display());
-                }
-            }
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
 
-            Ok(input)
-        }
-        Err(e) => Err(e).with_context(|| format!("file '{}' not found", path.display())),
+    let part: usize = args
+        .next_parsed("part")?
+        .context("argument 'part' not provided")?;
+
+    let day = runner::find(days, year, day)?;
+    day.part(part)?;
+
+    watch::Watch {
+        year,
+        day: day.day,
+        part,
+        source: Source::new(user, ""),
+    }
+    .run()
+}
+
+// plays a day's frames in the terminal, or writes them to `--out`
+fn visualize(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let fps = args.value_parsed("fps")?.unwrap_or(30.0);
+    let out = args.value("out")?;
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
+
+    let day = runner::find(days, year, day)?;
+
+    let visualize = day
+        .visualize
+        .with_context(|| format!("day {} has no visualisation", day.day))?;
+
+    let (_, input) = read_input(day.year, day.day, user, &mut args)?;
+
+    let mut frames = match &out {
+        Some(dir) => runner::Frames::directory(dir)?,
+        None => runner::Frames::terminal(fps)?,
+    };
+
+    visualize(input, &mut frames)?;
+
+    if let Some(dir) = &out {
+        println!("wrote {} frames to '{dir}'", frames.count());
+    }
+
+    Ok(())
+}
+
+// works before a day has any code, so only the puzzle's date is checked
+fn inspect_input(mut args: Args) -> Result<()> {
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
+
+    if !(2015..=CUR_YEAR).contains(&year) {
+        anyhow::bail!("'{year}' is not a valid year");
+    }
+
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("'{day}' is not a valid day");
+    }
+
+    let (_, input) = read_input(year, day, user, &mut args)?;
+
+    inspect::print(&inspect::summarize(&input));
+
+    Ok(())
+}
+
+fn show_dashboard(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let source = Source::new(args.value("user")?, "");
+    let year = args.year(CUR_YEAR)?;
+
+    runner::check_year(days, year)?;
+
+    dashboard::Dashboard::new(year, days, source).run()
+}
+
+fn show_puzzle(mut args: Args) -> Result<()> {
+    let source = Source::new(args.value("user")?, "");
+    let refresh = args.flag("refresh");
+    let year = args.year(CUR_YEAR)?;
+
+    let day: usize = args
+        .next_parsed("day")?
+        .context("argument 'day' not provided")?;
+
+    let markdown = puzzle::load_description(year, day, &source, refresh)?;
+    puzzle::print(&markdown);
+
+    Ok(())
+}
+
+fn mock_server(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let port = args.value_parsed("port")?.unwrap_or(8025u16);
+    let unlocked = args.value_parsed("unlocked")?.unwrap_or(25);
+
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("could not listen on port {port}"))?;
+
+    println!("serving a mock puzzle site on http://127.0.0.1:{port}");
+    println!("use it with AOC_BASE_URL=http://127.0.0.1:{port} and any AOC_SESSION but '{}'", mock::EXPIRED_SESSION);
+
+    mock::MockServer::new(days, unlocked).serve(listener)
+}
+
+// an optional input file suffix, or `-` followed by the input itself
+fn read_input(year: usize, day: usize, user: Option<String>, args: &mut Args) -> Result<(Source, String)> {
+    let source = Source::new(user, args.next().unwrap_or_default());
+
+    if source.suffix == "-" {
+        return Ok((source, args.next().unwrap_or_default()));
+    }
+
+    let input = load_input_file(year, day, &source)?;
+    Ok((source, input))
+}
+
+fn run_all(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let outputs = Outputs::new(&mut args)?;
+    let parallel = args.flag("parallel");
+    let source = Source::new(args.value("user")?, "");
+    let year = args.year(CUR_YEAR)?;
+
+    runner::check_year(days, year)?;
+    let days: Vec<_> = days.iter().filter(|d| d.year == year).collect();
+
+    let report = runner::run_days(days, &[1, 2], 1, parallel, &source)?;
+    report.print_summary();
+
+    outputs.write(&report)
+}
+
+fn bench(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let outputs = Outputs::new(&mut args)?;
+    let iterations = args.value_parsed("iterations")?.unwrap_or(10);
+    let parallel = args.flag("parallel");
+    let source = Source::new(args.value("user")?, "");
+    let year = args.year(CUR_YEAR)?;
+    let (days, parts) = select_parts(days, year, &mut args)?;
+
+    let report = runner::run_days(days, &parts, iterations, parallel, &source)?;
+    report.print_summary();
+
+    outputs.write(&report)
+}
+
+// checks every cached input of one or all accounts against their recorded answers
+fn verify(days: &[runner::Day], mut args: Args) -> Result<()> {
+    let outputs = Outputs::new(&mut args)?;
+    let all_users = args.flag("all-users");
+    let allow_unknown = args.flag("allow-unknown");
+    let user = args.value("user")?;
+    let year = args.year(CUR_YEAR)?;
+    let (days, parts) = select_parts(days, year, &mut args)?;
+
+    let sources: Vec<Source> = match all_users {
+        true => std::iter::once(None)
+            .chain(input::users()?.into_iter().map(Some))
+            .map(|user| Source::new(user, ""))
+            .collect(),
+        false => vec![Source::new(user, "")],
+    };
+
+    let report = runner::verify(days, &parts, &sources)?;
+    report.print_summary();
+
+    outputs.write(&report)?;
+
+    let names = |statuses: &[Status]| -> Vec<String> {
+        report
+            .runs
+            .iter()
+            .filter(|run| statuses.contains(&run.status()))
+            .map(|run| match run.input.is_empty() {
+                true => format!("day {} part {}", run.day, run.part),
+                false => format!("day {} part {} [{}]", run.day, run.part, run.input),
+            })
+            .collect()
+    };
+
+    let failed = names(&[Status::Wrong, Status::Failed]);
+    let unknown = names(&[Status::Unknown]);
+
+    if report.runs.is_empty() {
+        anyhow::bail!("no cached inputs found");
+    }
+
+    // a run without a recorded answer hasn't been checked against anything
+    if !unknown.is_empty() {
+        println!("{} runs have no recorded answer: {}", unknown.len(), unknown.join(", "));
+    }
+
+    match (failed.is_empty(), unknown.is_empty() || allow_unknown) {
+        (true, true) => Ok(()),
+        (false, _) => anyhow::bail!("{} runs did not match: {}", failed.len(), failed.join(", ")),
+        (true, false) => anyhow::bail!(
+            "{} runs have no recorded answer, pass '--allow-unknown' to accept them",
+            unknown.len(),
+        ),
     }
 }
 
-fn download_input_file(day: usize) -> Result<String> {
-    let session =
-        std::env::var("AOC_SESSION").context("AOC_SESSION environment variable not found")?;
+// an optional day and part, defaulting to every day of the year and both parts
+fn select_parts<'a>(
+    days: &'a [runner::Day],
+    year: usize,
+    args: &mut Args,
+) -> Result<(Vec<&'a runner::Day>, Vec<usize>)> {
+    let day: Option<usize> = args.next_parsed("day")?;
+    let part: Option<usize> = args.next_parsed("part")?;
 
-    let url = format!("https://adventofcode.com/2024/day/{day}/input");
+    runner::check_year(days, year)?;
 
-    let resp = ureq::get(&url)
-        .set("Cookie", &format!("session={session}"))
-        .call()?;
+    let days: Vec<_> = match day {
+        Some(day) => vec![runner::find(days, year, day)?],
+        None => days.iter().filter(|d| d.year == year).collect(),
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
 
-    Ok(resp.into_string()?)
+    Ok((days, parts))
+}
+
+struct Outputs {
+    exports: Vec<String>,
+    trace: Option<String>,
+}
+
+impl Outputs {
+    fn new(args: &mut Args) -> Result<Self> {
+        Ok(Outputs {
+            exports: args.values("export")?,
+            trace: args.value("trace")?,
+        })
+    }
+
+    fn write(&self, report: &Report) -> Result<()> {
+        for path in &self.exports {
+            report.export(Path::new(path))?;
+            println!("wrote report to '{path}'");
+        }
+
+        if let Some(path) = &self.trace {
+            report.export_trace(Path::new(path))?;
+            println!("wrote trace to '{path}'");
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,481 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    panic::AssertUnwindSafe,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    runner::{Answer, Day},
+    util::{Json, Rng},
+};
+
+// a session that the mock treats as logged out
+pub const EXPIRED_SESSION: &str = "expired";
+
+const COOLDOWN: Duration = Duration::from_secs(60);
+
+// a stand-in for the puzzle site, serving generated inputs of days that have
+// a generator and checking submissions against the registered parts
+pub struct MockServer<'a> {
+    days: &'a [Day],
+    // days after this one are still locked
+    unlocked: usize,
+    puzzles: HashMap<(usize, usize), Puzzle>,
+    locked_until: Option<Instant>,
+}
+
+struct Puzzle {
+    input: String,
+    answers: [Option<Answer>; 2],
+    solved: usize,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    session: Option<String>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: impl Into<String>) -> Self {
+        Response { status, content_type, body: body.into() }
+    }
+
+    fn html(body: impl Into<String>) -> Self {
+        Self::new(200, "text/html", body)
+    }
+
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Self::new(status, "text/plain", body)
+    }
+}
+
+impl<'a> MockServer<'a> {
+    pub fn new(days: &'a [Day], unlocked: usize) -> Self {
+        MockServer {
+            days,
+            unlocked,
+            puzzles: HashMap::new(),
+            locked_until: None,
+        }
+    }
+
+    pub fn serve(&mut self, listener: TcpListener) -> Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream.context("could not accept connection")?;
+
+            if let Err(e) = self.handle(stream) {
+                log::warn!("request failed: {e:?}");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle(&mut self, mut stream: TcpStream) -> Result<()> {
+        let request = read_request(&mut stream)?;
+        let response = self.respond(&request);
+
+        log::info!("{} {} -> {}", request.method, request.path, response.status);
+
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            reason(response.status),
+            response.content_type,
+            response.body.len(),
+            response.body,
+        )?;
+
+        Ok(())
+    }
+
+    fn respond(&mut self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+        let logged_in = request.session.as_deref().is_some_and(|s| s != EXPIRED_SESSION);
+
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", [year]) => match year.parse() {
+                Ok(year) => self.calendar(year, logged_in),
+                Err(_) => Response::text(404, "404 Not Found"),
+            },
+            (_, [year, "day", day, rest @ ..]) => {
+                let (Ok(year), Ok(day)) = (year.parse(), day.parse())
+                else { return Response::text(404, "404 Not Found") };
+
+                if !self.days.iter().any(|d| d.year == year && d.day == day) || day > self.unlocked {
+                    return Response::text(
+                        404,
+                        "Please don't repeatedly request this endpoint before it unlocks! \
+                         The calendar countdown is synchronized with the server time; \
+                         the link will be enabled on the calendar the instant this puzzle becomes available.",
+                    );
+                }
+
+                match (request.method.as_str(), rest) {
+                    ("GET", []) => self.puzzle_page(year, day),
+                    ("GET", ["input"]) if !logged_in => Response::text(
+                        400,
+                        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+                    ),
+                    ("GET", ["input"]) => match self.puzzle(year, day) {
+                        Some(puzzle) => Response::text(200, puzzle.input.clone()),
+                        None => Response::text(404, "this day has no input generator"),
+                    },
+                    ("POST", ["answer"]) if !logged_in => {
+                        Response::text(400, "To play, please identify yourself via one of these services.")
+                    }
+                    ("POST", ["answer"]) => self.answer(year, day, &request.body),
+                    _ => Response::text(404, "404 Not Found"),
+                }
+            }
+            // like the site, logged out visitors get a page instead of the json
+            ("GET", [_, "leaderboard", "private", "view", _]) if !logged_in => {
+                Response::html("<html><body>You need to log in to view private leaderboards.</body></html>")
+            }
+            ("GET", [year, "leaderboard", "private", "view", id]) => {
+                match (year.parse(), id.strip_suffix(".json")) {
+                    (Ok(year), Some(id)) => self.leaderboard(year, id),
+                    _ => Response::text(404, "404 Not Found"),
+                }
+            }
+            _ => Response::text(404, "404 Not Found"),
+        }
+    }
+
+    // inputs come from the day's generator, seeded by the day so they don't change
+    fn puzzle(&mut self, year: usize, day: usize) -> Option<&mut Puzzle> {
+        let registered = self.days.iter().find(|d| d.year == year && d.day == day)?;
+        let generate = registered.generator?;
+
+        let puzzle = self.puzzles.entry((year, day)).or_insert_with(|| {
+            let input = generate(&mut Rng::new((year * 100 + day) as u64));
+            // a panicking part leaves its answer unknown instead of ending the server
+            let answers = [1, 2].map(|part| {
+                let variant = registered.part(part).ok()?;
+
+                match std::panic::catch_unwind(AssertUnwindSafe(|| (variant.func)(input.clone()))) {
+                    Ok(answer) => answer.ok(),
+                    Err(_) => {
+                        log::warn!("part {part} of y{year} day {day} panicked on the mock input");
+                        None
+                    }
+                }
+            });
+
+            Puzzle { input, answers, solved: 0 }
+        });
+
+        Some(puzzle)
+    }
+
+    fn solved(&self, year: usize, day: usize) -> usize {
+        self.puzzles.get(&(year, day)).map_or(0, |p| p.solved)
+    }
+
+    fn calendar(&self, year: usize, logged_in: bool) -> Response {
+        let mut page = String::from("<html><body><header>");
+
+        page.push_str(&match logged_in {
+            true => format!("<a href=\"/{year}/auth/logout\">[Log Out]</a>"),
+            false => format!("<a href=\"/{year}/auth/login\">[Log In]</a>"),
+        });
+
+        page.push_str("</header><main><pre class=\"calendar\">\n");
+
+        for day in self.days.iter().filter(|d| d.year == year && d.day <= self.unlocked).map(|d| d.day) {
+            let solved = match logged_in {
+                true => self.solved(year, day),
+                false => 0,
+            };
+
+            let label = match solved {
+                2 => format!("Day {day}, two stars"),
+                1 => format!("Day {day}, one star"),
+                _ => format!("Day {day}"),
+            };
+
+            page.push_str(&format!(
+                "<a aria-label=\"{label}\" href=\"/{year}/day/{day}\" class=\"calendar-day{day}\">day {day}</a>\n"
+            ));
+        }
+
+        page.push_str("</pre></main></body></html>");
+        Response::html(page)
+    }
+
+    fn puzzle_page(&self, year: usize, day: usize) -> Response {
+        let mut page = format!(
+            "<html><body><main>\n<article class=\"day-desc\"><h2>--- Day {day}: Mock Puzzle ---</h2>\
+             <p>This is a <em>mock</em> puzzle served for testing, get your \
+             <a href=\"/{year}/day/{day}/input\" target=\"_blank\">puzzle input</a>.</p>\
+             <p>For example:</p>\n<pre><code>1 2 3\n4 5 6\n</code></pre>\
+             <p>The answer is <code><em>42</em></code>, of course.</p></article>\n"
+        );
+
+        // part 2 is only revealed once part 1 is solved
+        if let Some(puzzle) = self.puzzles.get(&(year, day)).filter(|p| p.solved >= 1) {
+            let answer = puzzle.answers[0].as_ref().map(Answer::as_str).unwrap_or_default();
+
+            page.push_str(&format!("<p>Your puzzle answer was <code>{answer}</code>.</p>"));
+            page.push_str(
+                "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+                 <p>Now do it <em>again</em>, but harder.</p></article>\n",
+            );
+        }
+
+        page.push_str("</main></body></html>");
+        Response::html(page)
+    }
+
+    fn answer(&mut self, year: usize, day: usize, body: &str) -> Response {
+        let form: HashMap<&str, String> = body
+            .split('&')
+            .filter_map(|field| field.split_once('='))
+            .map(|(key, value)| (key, decode_form(value)))
+            .collect();
+
+        let level: usize = form.get("level").and_then(|l| l.parse().ok()).unwrap_or(0);
+        let answer = form.get("answer").cloned().unwrap_or_default();
+
+        if let Some(wait) = self.locked_until.and_then(|until| until.checked_duration_since(Instant::now())) {
+            return article(&format!(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  \
+                 You have {}s left to wait.",
+                wait.as_secs() + 1,
+            ));
+        }
+
+        let Some(puzzle) = self.puzzle(year, day)
+        else { return Response::text(404, "this day has no input generator") };
+
+        if level != puzzle.solved + 1 || level > 2 {
+            return article("You don't seem to be solving the right level.  Did you already complete it?");
+        }
+
+        let Some(expected) = &puzzle.answers[level - 1]
+        else { return Response::text(500, "the registered part failed on the mock input") };
+
+        if answer == expected.as_str() {
+            puzzle.solved = level;
+            return article("That's the right answer!  You are one gold star closer to finding the Chief Historian.");
+        }
+
+        let hint = match (answer.parse::<i64>(), expected.as_str().parse::<i64>()) {
+            (Ok(a), Ok(e)) if a > e => "  Your answer is too high.",
+            (Ok(a), Ok(e)) if a < e => "  Your answer is too low.",
+            _ => "",
+        };
+
+        self.locked_until = Some(Instant::now() + COOLDOWN);
+
+        article(&format!(
+            "That's not the right answer.{hint}  Please wait one minute before trying again.",
+        ))
+    }
+
+    fn leaderboard(&self, year: usize, id: &str) -> Response {
+        let mut days = Vec::new();
+
+        for ((puzzle_year, day), puzzle) in &self.puzzles {
+            if *puzzle_year == year && puzzle.solved > 0 {
+                let levels = (1..=puzzle.solved)
+                    .map(|level| (level.to_string(), Json::object([("get_star_ts", 0usize.into())])));
+                days.push((day.to_string(), Json::object(levels)));
+            }
+        }
+
+        let stars: usize = self.puzzles.values().map(|p| p.solved).sum();
+
+        let member = Json::object([
+            ("id", 1usize.into()),
+            ("name", "mock user".into()),
+            ("stars", stars.into()),
+            ("local_score", (stars * 10).into()),
+            ("global_score", 0usize.into()),
+            ("completion_day_level", Json::object(days)),
+        ]);
+
+        let json = Json::object([
+            ("event", year.to_string().into()),
+            ("owner_id", id.into()),
+            ("members", Json::object([("1", member)])),
+        ]);
+
+        Response::new(200, "application/json", json.to_string())
+    }
+}
+
+fn article(message: &str) -> Response {
+    Response::html(format!("<html><body><main><article><p>{message}</p></article></main></body></html>"))
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("empty request")?.to_string();
+    let path = parts.next().context("request has no path")?.to_string();
+
+    let mut session = None;
+    let mut length = 0;
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        let Some((name, value)) = header.split_once(':')
+        else { continue };
+
+        match name.to_ascii_lowercase().as_str() {
+            "content-length" => length = value.trim().parse().context("invalid content length")?,
+            "cookie" => {
+                session = value
+                    .split(';')
+                    .filter_map(|cookie| cookie.trim().strip_prefix("session="))
+                    .next()
+                    .map(str::to_string);
+            }
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        session,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn decode_form(value: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut iter = value.bytes();
+
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex: Vec<u8> = iter.by_ref().take(2).collect();
+                let decoded = std::str::from_utf8(&hex).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                bytes.extend(decoded);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Client, Verdict};
+
+    fn part1(input: String) -> Result<Answer> {
+        Ok(input.trim().parse::<u64>()?.into())
+    }
+
+    fn part2(input: String) -> Result<Answer> {
+        Ok((input.trim().parse::<u64>()? * 2).into())
+    }
+
+    fn panics(_: String) -> Result<Answer> {
+        panic!("part failed")
+    }
+
+    fn generate(rng: &mut Rng) -> String {
+        format!("{}\n", rng.range(10..100))
+    }
+
+    // a server on an ephemeral port, with day 2's first part panicking
+    fn start() -> String {
+        let days = vec![
+            Day::new(2024, 1, [part1, part2]).generator(("generate", generate)),
+            Day::new(2024, 2, [panics, part2]).generator(("generate", generate)),
+        ];
+        let days: &'static [Day] = Box::leak(days.into_boxed_slice());
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || MockServer::new(days, 25).serve(listener));
+
+        url
+    }
+
+    #[test]
+    fn fetch_and_submit() {
+        let client = Client::with_session(start(), "session");
+
+        let input = client.get("/2024/day/1/input").unwrap();
+        let answer: u64 = input.trim().parse().unwrap();
+
+        assert_eq!(client.get("/2024/day/1/input").unwrap(), input);
+        assert!(client.get("/2024").unwrap().contains("aria-label=\"Day 1\""));
+
+        assert_eq!(client.submit(2024, 1, 2, "1").unwrap(), Verdict::WrongLevel);
+        assert_eq!(client.submit(2024, 1, 1, &answer.to_string()).unwrap(), Verdict::Correct);
+        assert!(client.get("/2024").unwrap().contains("aria-label=\"Day 1, one star\""));
+
+        assert_eq!(
+            client.submit(2024, 1, 2, "1").unwrap(),
+            Verdict::Wrong(Some("your answer is too low".to_string())),
+        );
+        assert!(matches!(client.submit(2024, 1, 2, "1").unwrap(), Verdict::TooRecent(Some(_))));
+    }
+
+    #[test]
+    fn logged_out() {
+        let url = start();
+        let client = Client::with_session(&url, EXPIRED_SESSION);
+
+        let error = client.get("/2024/day/1/input").unwrap_err();
+        assert!(error.to_string().contains("Please log in"), "{error}");
+
+        assert!(client.submit(2024, 1, 1, "1").is_err());
+        assert!(client.get("/2024/leaderboard/private/view/1.json").unwrap().contains("log in"));
+        assert!(!client.get("/2024").unwrap().contains("/auth/logout"));
+    }
+
+    #[test]
+    fn panicking_part() {
+        let client = Client::with_session(start(), "session");
+
+        assert!(client.submit(2024, 2, 1, "1").is_err());
+        assert_eq!(client.get("/2024/day/2/input").unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,414 @@
+use std::{io::IsTerminal, path::PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    client::{BASE_URL, Client},
+    input::{Source, load_answers},
+    util::terminal::{BOLD, CYAN, DIM, RESET, UNDERLINE},
+    vault,
+};
+
+pub fn description_path(year: usize, day: usize, source: &Source) -> PathBuf {
+    source.dir(year).join(format!("{day}.md"))
+}
+
+// the puzzle text as markdown, fetched again once part 1 is solved so the
+// cached copy picks up the part 2 description
+pub fn load_description(year: usize, day: usize, source: &Source, refresh: bool) -> Result<String> {
+    let path = description_path(year, day, source);
+
+    let cached = match refresh {
+        true => None,
+        false => vault::read(&path).ok(),
+    };
+
+    if let Some(text) = &cached {
+        let has_part2 = text.lines().filter(|line| line.starts_with("## ")).count() >= 2;
+        let solved_part1 = load_answers(year, day, source)?[0].is_some();
+
+        if has_part2 || !solved_part1 {
+            return Ok(cached.unwrap());
+        }
+
+        log::info!("part 1 is solved, fetching the part 2 description");
+    }
+
+    let page = Client::new(source.user.as_deref())?.get(&format!("/{year}/day/{day}"))?;
+    let markdown = to_markdown(&page)?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+
+    vault::write(&path, &markdown)
+        .with_context(|| format!("could not write description to '{}'", path.display()))?;
+
+    Ok(markdown)
+}
+
+// converts every `<article class="day-desc">` of a puzzle page
+pub fn to_markdown(page: &str) -> Result<String> {
+    let articles: Vec<&str> = page
+        .split("<article class=\"day-desc\">")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("</article>").map(|(article, _)| article))
+        .collect();
+
+    if articles.is_empty() {
+        anyhow::bail!("page has no puzzle description, is the day unlocked?");
+    }
+
+    let mut out = Markdown::default();
+
+    for article in articles {
+        out.article(article);
+    }
+
+    Ok(out.text.trim_end().to_string() + "\n")
+}
+
+#[derive(Default)]
+struct Markdown {
+    text: String,
+    in_pre: bool,
+    in_code: bool,
+    // `<code>` was opened but nothing written yet, so an `<em>` can wrap it
+    code_pending: bool,
+    em_around_code: bool,
+    links: Vec<String>,
+}
+
+impl Markdown {
+    fn article(&mut self, html: &str) {
+        let mut rest = html;
+
+        while !rest.is_empty() {
+            match rest.find('<') {
+                Some(0) => {
+                    let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                    self.tag(&rest[..end]);
+                    rest = &rest[end..];
+                }
+                Some(i) => {
+                    self.text(&rest[..i]);
+                    rest = &rest[i..];
+                }
+                None => {
+                    self.text(rest);
+                    rest = "";
+                }
+            }
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let inner = tag.trim_start_matches('<').trim_end_matches('>');
+        let closing = inner.starts_with('/');
+        let name = inner
+            .trim_start_matches('/')
+            .split(|ch: char| ch.is_whitespace() || ch == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, closing) {
+            ("h2", false) => self.text.push_str("## "),
+            ("h2" | "p" | "ul", true) => self.end_line(2),
+            ("li", false) => self.text.push_str("- "),
+            ("li", true) => self.end_line(1),
+            ("pre", false) => {
+                self.text.push_str("```\n");
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                self.end_line(1);
+                self.text.push_str("```");
+                self.end_line(2);
+                self.in_pre = false;
+            }
+            ("code", false) if !self.in_pre => {
+                self.in_code = true;
+                self.code_pending = true;
+            }
+            ("code", true) if !self.in_pre => {
+                if !self.code_pending {
+                    self.text.push('`');
+                }
+                if self.em_around_code {
+                    self.text.push('*');
+                }
+                self.in_code = false;
+                self.code_pending = false;
+                self.em_around_code = false;
+            }
+            ("em", false) if self.code_pending => {
+                self.text.push('*');
+                self.em_around_code = true;
+            }
+            ("em", _) if self.in_code || self.in_pre => {}
+            ("em", _) => self.text.push('*'),
+            ("a", false) => {
+                let href = attribute(inner, "href").unwrap_or_default();
+                let href = match href.starts_with('/') {
+                    true => format!("{BASE_URL}{href}"),
+                    false => href,
+                };
+
+                self.links.push(href);
+                self.text.push('[');
+            }
+            ("a", true) => {
+                let href = self.links.pop().unwrap_or_default();
+                self.text.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    // ends the current line followed by `newlines - 1` blank lines, however
+    // many line breaks the html already had
+    fn end_line(&mut self, newlines: usize) {
+        let trimmed = self.text.trim_end().len();
+        self.text.truncate(trimmed);
+
+        if !self.text.is_empty() {
+            self.text.push_str(&"\n".repeat(newlines));
+        }
+    }
+
+    fn text(&mut self, html: &str) {
+        let text = decode_entities(html);
+
+        if self.in_pre {
+            self.text.push_str(&text);
+            return;
+        }
+
+        // paragraphs are single lines, the line breaks in the html are just wrapping
+        let mut text = text.replace('\n', " ");
+
+        if self.text.is_empty() || self.text.ends_with('\n') {
+            text = text.trim_start().to_string();
+        }
+
+        if text.is_empty() {
+            return;
+        }
+
+        if self.code_pending {
+            self.text.push('`');
+            self.code_pending = false;
+        }
+
+        if self.in_code {
+            self.text.push_str(&text);
+            return;
+        }
+
+        for ch in text.chars() {
+            if matches!(ch, '*' | '`' | '[' | ']' | '\\') {
+                self.text.push('\\');
+            }
+            self.text.push(ch);
+        }
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let (_, rest) = tag.split_once(&format!("{name}=\""))?;
+    rest.split_once('"').map(|(value, _)| decode_entities(value))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        let Some(end) = rest.find(';').filter(|end| *end <= 8)
+        else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let decoded = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            entity => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+
+        match decoded {
+            Some(ch) => {
+                out.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+const CODE: &str = CYAN;
+const LINK: &str = UNDERLINE;
+
+const WIDTH: usize = 80;
+
+// prints the markdown with terminal styling, or as is when piped
+pub fn print(markdown: &str) {
+    if !std::io::stdout().is_terminal() {
+        print!("{markdown}");
+        return;
+    }
+
+    for line in render(markdown) {
+        println!("{line}");
+    }
+}
+
+// the lines of the markdown with terminal styling, wrapped to the width
+pub fn render(markdown: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        if line == "```" {
+            in_fence = !in_fence;
+            continue;
+        }
+
+        if line.is_empty() {
+            lines.push(String::new());
+        } else if in_fence {
+            lines.push(format!("    {CODE}{line}{RESET}"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            lines.push(format!("{BOLD}{}{RESET}", inline(heading)));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            for (i, line) in wrap(&inline(item), WIDTH - 2).into_iter().enumerate() {
+                lines.push(format!("{}{line}", if i == 0 { "- " } else { "  " }));
+            }
+        } else {
+            lines.extend(wrap(&inline(line), WIDTH));
+        }
+    }
+
+    lines
+}
+
+// styles `*emphasis*`, `code` and [links](url)
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+
+    let (mut bold, mut code) = (false, false);
+    let mut link = false;
+
+    // styles are re-applied after every reset since they can nest
+    let style = |bold: bool, code: bool, link: bool| {
+        let mut style = RESET.to_string();
+        if bold {
+            style.push_str(BOLD);
+        }
+        if code {
+            style.push_str(CODE);
+        }
+        if link {
+            style.push_str(LINK);
+        }
+        style
+    };
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if !code => out.extend(chars.next()),
+            '`' => {
+                code = !code;
+                out.push_str(&style(bold, code, link));
+            }
+            '*' if !code => {
+                bold = !bold;
+                out.push_str(&style(bold, code, link));
+            }
+            '[' if !code => {
+                link = true;
+                out.push_str(&style(bold, code, link));
+            }
+            ']' if !code && link => {
+                link = false;
+                out.push_str(&style(bold, code, link));
+
+                // the url of a link is only worth showing when it leaves the puzzle
+                let rest = chars.as_str();
+                if let Some(url) = rest.strip_prefix('(').and_then(|rest| rest.split_once(')')) {
+                    if !url.0.starts_with(BASE_URL) {
+                        out.push_str(&format!(" {DIM}<{}>{}", url.0, style(bold, code, link)));
+                    }
+                    chars = url.1.chars();
+                }
+            }
+            ch => out.push(ch),
+        }
+    }
+
+    out.push_str(RESET);
+    out
+}
+
+// wraps on spaces, not counting escape sequences towards the width
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let visible = |word: &str| {
+        let mut len = 0;
+        let mut escape = false;
+
+        for ch in word.chars() {
+            match ch {
+                '\x1b' => escape = true,
+                'm' if escape => escape = false,
+                _ if !escape => len += 1,
+                _ => {}
+            }
+        }
+
+        len
+    };
+
+    let mut lines = vec![String::new()];
+    let mut len = 0;
+
+    for word in text.split(' ') {
+        let word_len = visible(word);
+
+        if len > 0 && len + 1 + word_len > width {
+            lines.push(String::new());
+            len = 0;
+        }
+
+        let line = lines.last_mut().unwrap();
+
+        if len > 0 {
+            line.push(' ');
+            len += 1;
+        }
+
+        line.push_str(word);
+        len += word_len;
+    }
+
+    lines
+}
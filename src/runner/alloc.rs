@@ -0,0 +1,215 @@
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub bytes: usize,
+    pub count: usize,
+    pub peak: usize,
+}
+
+struct Global {
+    bytes: AtomicUsize,
+    count: AtomicUsize,
+    live: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+static GLOBAL: Global = Global {
+    bytes: AtomicUsize::new(0),
+    count: AtomicUsize::new(0),
+    live: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+// live memory of a single thread can go below its starting point when it frees
+// memory allocated elsewhere, so it's tracked relative to a wrapping origin
+struct Local {
+    untracked: Cell<bool>,
+    bytes: Cell<usize>,
+    count: Cell<usize>,
+    live: Cell<usize>,
+    peak: Cell<usize>,
+}
+
+thread_local! {
+    static LOCAL: Local = const {
+        Local {
+            untracked: Cell::new(false),
+            bytes: Cell::new(0),
+            count: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::Ordering,
+    };
+
+    use super::{GLOBAL, LOCAL};
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    struct Counting;
+
+    impl Counting {
+        fn untracked() -> bool {
+            LOCAL.try_with(|local| local.untracked.get()).unwrap_or(false)
+        }
+
+        fn grow(size: usize) {
+            if Self::untracked() {
+                return;
+            }
+
+            GLOBAL.bytes.fetch_add(size, Ordering::Relaxed);
+            GLOBAL.count.fetch_add(1, Ordering::Relaxed);
+            let live = GLOBAL.live.fetch_add(size, Ordering::Relaxed) + size;
+            GLOBAL.peak.fetch_max(live, Ordering::Relaxed);
+
+            let _ = LOCAL.try_with(|local| {
+                local.bytes.set(local.bytes.get() + size);
+                local.count.set(local.count.get() + 1);
+                let live = local.live.get().wrapping_add(size);
+                local.live.set(live);
+                if (live as isize) > (local.peak.get() as isize) {
+                    local.peak.set(live);
+                }
+            });
+        }
+
+        fn shrink(size: usize) {
+            if Self::untracked() {
+                return;
+            }
+
+            GLOBAL.live.fetch_sub(size, Ordering::Relaxed);
+
+            let _ = LOCAL.try_with(|local| local.live.set(local.live.get().wrapping_sub(size)));
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            Self::shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                Self::shrink(layout.size());
+                Self::grow(new_size);
+            }
+            new_ptr
+        }
+    }
+}
+
+// runs `f` without counting its allocations, for the runner's own threads that
+// work next to a measured part; memory they free must also be theirs
+pub fn untracked<T>(f: impl FnOnce() -> T) -> T {
+    let previous = LOCAL.with(|local| local.untracked.replace(true));
+    let result = f();
+    LOCAL.with(|local| local.untracked.set(previous));
+
+    result
+}
+
+pub struct Measure {
+    thread: bool,
+    bytes: usize,
+    count: usize,
+    live: usize,
+}
+
+// `thread` only counts allocations made on the calling thread, which is only
+// meaningful for parts that don't hand work to other threads
+pub fn measure(thread: bool) -> Option<Measure> {
+    if !cfg!(feature = "alloc-stats") {
+        return None;
+    }
+
+    let (bytes, count, live) = match thread {
+        true => LOCAL.with(|local| {
+            local.peak.set(local.live.get());
+            (local.bytes.get(), local.count.get(), local.live.get())
+        }),
+        false => {
+            let live = GLOBAL.live.load(Ordering::Relaxed);
+            GLOBAL.peak.store(live, Ordering::Relaxed);
+            (
+                GLOBAL.bytes.load(Ordering::Relaxed),
+                GLOBAL.count.load(Ordering::Relaxed),
+                live,
+            )
+        }
+    };
+
+    Some(Measure {
+        thread,
+        bytes,
+        count,
+        live,
+    })
+}
+
+impl Measure {
+    pub fn finish(self) -> AllocStats {
+        let (bytes, count, peak) = match self.thread {
+            true => LOCAL.with(|local| (local.bytes.get(), local.count.get(), local.peak.get())),
+            false => (
+                GLOBAL.bytes.load(Ordering::Relaxed),
+                GLOBAL.count.load(Ordering::Relaxed),
+                GLOBAL.peak.load(Ordering::Relaxed),
+            ),
+        };
+
+        AllocStats {
+            bytes: bytes - self.bytes,
+            count: count - self.count,
+            peak: (peak.wrapping_sub(self.live) as isize).max(0) as usize,
+        }
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{value:.1} {}", UNITS[unit]),
+    }
+}
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuClock {
+    // only valid while the part runs on the calling thread alone
+    Thread,
+    // only valid while nothing else runs in the process
+    Process,
+}
+
+impl CpuClock {
+    pub fn now(self) -> Duration {
+        let clock = match self {
+            CpuClock::Thread => libc::CLOCK_THREAD_CPUTIME_ID,
+            CpuClock::Process => libc::CLOCK_PROCESS_CPUTIME_ID,
+        };
+
+        let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+
+        // SAFETY: `time` is a valid, writable timespec
+        let res = unsafe { libc::clock_gettime(clock, &mut time) };
+
+        if res != 0 {
+            return Duration::ZERO;
+        }
+
+        Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+}
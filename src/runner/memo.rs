@@ -0,0 +1,87 @@
+use std::sync::{Mutex, PoisonError};
+
+use cached::{Cached, SizedCache, UnboundCache};
+
+// a cache that outlives a run, like the static behind a `#[cached]` function,
+// which the runner empties before every timed run of its day
+pub trait Memo: Sync {
+    fn clear(&self);
+    fn entries(&self) -> usize;
+}
+
+// days register a function returning their cache, since the statics of
+// `#[cached]` can't be named in `years!` directly
+pub type MemoFn = fn() -> &'static dyn Memo;
+
+macro_rules! memo_for_cached {
+    ($($cache:ident),*) => {
+        $(impl<K, V> Memo for Mutex<$cache<K, V>>
+        where
+            $cache<K, V>: Cached<K, V> + Send,
+        {
+            fn clear(&self) {
+                self.lock().unwrap_or_else(PoisonError::into_inner).cache_clear();
+            }
+
+            fn entries(&self) -> usize {
+                self.lock().unwrap_or_else(PoisonError::into_inner).cache_size()
+            }
+        })*
+    };
+}
+
+memo_for_cached!(UnboundCache, SizedCache);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use anyhow::Result;
+    use cached::proc_macro::cached;
+
+    use super::*;
+    use crate::{
+        input::Source,
+        runner::{Answer, CpuClock, Day, run_part},
+    };
+
+    static COMPUTED: AtomicUsize = AtomicUsize::new(0);
+
+    #[cached]
+    fn square(n: u64) -> u64 {
+        COMPUTED.fetch_add(1, Ordering::Relaxed);
+        n * n
+    }
+
+    fn square_cache() -> &'static dyn Memo {
+        &*SQUARE
+    }
+
+    fn part(input: String) -> Result<Answer> {
+        Ok(square(input.trim().parse()?).into())
+    }
+
+    #[test]
+    fn clear_empties_cache() {
+        let cache = Mutex::new(UnboundCache::new());
+        cache.lock().unwrap().cache_set(1, 2);
+        cache.lock().unwrap().cache_set(3, 4);
+
+        assert_eq!(cache.entries(), 2);
+        cache.clear();
+        assert_eq!(cache.entries(), 0);
+    }
+
+    // every iteration has to do the work again instead of reading the last one's cache
+    #[test]
+    fn iterations_start_with_empty_caches() {
+        let day = Day::new(2024, 1, [part, part]).memo(("square_cache", square_cache));
+
+        let run = run_part(&day, day.part(1).unwrap(), &Source::new(None, "-"), "12".to_string(), 5, CpuClock::Process)
+            .unwrap();
+
+        assert_eq!(run.answer, Ok(Answer::from(144u64)));
+        assert_eq!(COMPUTED.load(Ordering::Relaxed), 5);
+        assert_eq!(run.memos, vec![("square_cache", 1)]);
+    }
+}
@@ -0,0 +1,487 @@
+pub mod alloc;
+pub mod clock;
+pub mod memo;
+pub mod params;
+pub mod phase;
+pub mod progress;
+pub mod report;
+pub mod stress;
+pub mod visual;
+
+use std::{
+    fmt::Display,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    input::{Source, input_path, load_answers, load_input_file},
+    util::{
+        Grid, Rng,
+        ocr::{self, Pixel},
+    },
+};
+
+pub use self::{
+    alloc::AllocStats,
+    clock::CpuClock,
+    memo::{Memo, MemoFn},
+    params::param,
+    phase::{Span, phase},
+    progress::{Progress, progress},
+    report::Report,
+    visual::{Color, Frame, Frames, VisualizeFn},
+};
+
+pub type PartFn = fn(String) -> Result<Answer>;
+pub type GenFn = fn(&mut Rng) -> String;
+
+pub struct Variant {
+    pub part: usize,
+    pub name: &'static str,
+    pub func: PartFn,
+}
+
+pub struct Day {
+    pub year: usize,
+    pub day: usize,
+    // `part1` and `part2` first, followed by alternative implementations
+    pub variants: Vec<Variant>,
+    pub uses_rayon: bool,
+    // produces small random inputs for the `stress` command
+    pub generator: Option<GenFn>,
+    // caches kept between calls, emptied before every timed run
+    pub memos: Vec<(&'static str, MemoFn)>,
+    // draws the solution for the `visualize` command
+    pub visualize: Option<VisualizeFn>,
+}
+
+impl Day {
+    pub fn new(year: usize, day: usize, [part1, part2]: [PartFn; 2]) -> Self {
+        Day {
+            year,
+            day,
+            variants: vec![
+                Variant { part: 1, name: "part1", func: part1 },
+                Variant { part: 2, name: "part2", func: part2 },
+            ],
+            uses_rayon: false,
+            generator: None,
+            memos: Vec::new(),
+            visualize: None,
+        }
+    }
+
+    // the day uses rayon itself, so it is never run next to other days
+    pub fn uses_rayon(mut self) -> Self {
+        self.uses_rayon = true;
+        self
+    }
+
+    // another implementation of a part, named after it like `part2_fast`
+    pub fn variant(mut self, (name, func): (&'static str, PartFn)) -> Self {
+        let part = match name.strip_prefix("part").and_then(|rest| rest.chars().next()) {
+            Some('1') => 1,
+            Some('2') => 2,
+            _ => panic!("variant '{name}' of day {} must start with 'part1' or 'part2'", self.day),
+        };
+
+        self.variants.push(Variant { part, name, func });
+        self
+    }
+
+    pub fn generator(mut self, (_, func): (&'static str, GenFn)) -> Self {
+        self.generator = Some(func);
+        self
+    }
+
+    pub fn memo(mut self, memo: (&'static str, MemoFn)) -> Self {
+        self.memos.push(memo);
+        self
+    }
+
+    pub fn visualize(mut self, (_, func): (&'static str, VisualizeFn)) -> Self {
+        self.visualize = Some(func);
+        self
+    }
+
+    pub fn part(&self, part: usize) -> Result<&Variant> {
+        self.variants
+            .iter()
+            .find(|v| v.part == part)
+            .with_context(|| format!("'{part}' is not a valid part"))
+    }
+
+    pub fn variants_of(&self, part: usize) -> impl Iterator<Item = &Variant> {
+        self.variants.iter().filter(move |v| v.part == part)
+    }
+
+    pub fn find_variant(&self, name: &str) -> Result<&Variant> {
+        self.variants
+            .iter()
+            .find(|v| v.name == name)
+            .with_context(|| format!("day {} has no variant '{name}'", self.day))
+    }
+}
+
+// years in `years!` only have days when their feature is enabled
+pub fn check_year(days: &[Day], year: usize) -> Result<()> {
+    if days.iter().any(|d| d.year == year) {
+        return Ok(());
+    }
+
+    match crate::YEARS.contains(&year) {
+        true => anyhow::bail!("year {year} is not compiled in, enable feature 'y{year}'"),
+        false => anyhow::bail!("'{year}' is not a valid year"),
+    }
+}
+
+pub fn find(days: &[Day], year: usize, day: usize) -> Result<&Day> {
+    check_year(days, year)?;
+
+    days.iter()
+        .find(|d| d.year == year && d.day == day)
+        .with_context(|| format!("'{day}' is not a valid day"))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Answer(String);
+
+impl Answer {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+macro_rules! answer_from {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for Answer {
+            fn from(value: $ty) -> Self {
+                Answer(value.to_string())
+            }
+        })*
+    };
+}
+
+answer_from!(u32, u64, usize, i32, i64, isize, &str, String);
+
+// the letters drawn by a grid's pixels, for puzzles answering with a picture
+impl<T: Pixel> TryFrom<&Grid<T>> for Answer {
+    type Error = anyhow::Error;
+
+    fn try_from(grid: &Grid<T>) -> Result<Self> {
+        ocr::decode(grid).map(Answer)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unknown,
+    Failed,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unknown => "unknown",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+pub struct PartRun {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub variant: &'static str,
+    pub input: String,
+    pub answer: Result<Answer, String>,
+    pub expected: Option<Answer>,
+    pub times: Vec<Duration>,
+    pub cpu_times: Vec<Duration>,
+    // spans of the last iteration, the first one covering the whole part
+    pub phases: Vec<Span>,
+    // allocations of the last iteration, only tracked with the `alloc-stats` feature
+    pub alloc: Option<AllocStats>,
+    // overridden parameters the part read, with the values it got
+    pub params: Vec<(&'static str, String)>,
+    // entries in the day's memo caches after the last iteration
+    pub memos: Vec<(&'static str, usize)>,
+}
+
+impl PartRun {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Failed,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(answer), Some(expected)) if answer == expected => Status::Correct,
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        match self.status() {
+            Status::Correct => 1,
+            _ => 0,
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        median(&self.times)
+    }
+
+    pub fn cpu_median(&self) -> Duration {
+        median(&self.cpu_times)
+    }
+
+    pub fn min(&self) -> Duration {
+        self.times.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.times.iter().copied().max().unwrap_or_default()
+    }
+}
+
+fn median(times: &[Duration]) -> Duration {
+    let mut times = times.to_vec();
+    times.sort();
+
+    match times.len() {
+        0 => Duration::ZERO,
+        n if n % 2 == 1 => times[n / 2],
+        n => (times[n / 2 - 1] + times[n / 2]) / 2,
+    }
+}
+
+pub fn run_part(
+    day: &Day,
+    variant: &Variant,
+    source: &Source,
+    input: String,
+    iterations: usize,
+    clock: CpuClock,
+) -> Result<PartRun> {
+    let func = variant.func;
+
+    let expected = load_answers(day.year, day.day, source)?[variant.part - 1].clone();
+
+    let mut times = Vec::new();
+    let mut cpu_times = Vec::new();
+    let mut phases = Vec::new();
+    let mut alloc_stats = None;
+    let mut params = Vec::new();
+    let mut answer = Err(String::new());
+
+    // concurrent days would draw over each other's progress bars
+    let exclusive = clock == CpuClock::Process;
+
+    for _ in 0..iterations.max(1) {
+        let input = input.clone();
+
+        // otherwise every iteration after the first just reads the cache
+        for (_, memo) in &day.memos {
+            memo().clear();
+        }
+
+        // the progress bar's thread starts before and stops after the measured
+        // region, so only the part itself is timed
+        let (((result, spans), used), elapsed, cpu_elapsed, measured) = progress::track(exclusive, || {
+            let measure = alloc::measure(!exclusive);
+            let cpu_start = clock.now();
+            let start = Instant::now();
+            let out = params::record(|| phase::collect("part", || func(input)));
+            let end = Instant::now();
+            let cpu_end = clock.now();
+
+            (out, end - start, cpu_end.saturating_sub(cpu_start), measure.map(alloc::Measure::finish))
+        });
+        alloc_stats = measured;
+        params = used;
+
+        times.push(elapsed);
+        cpu_times.push(cpu_elapsed);
+        phases = spans;
+
+        match result {
+            Ok(a) => answer = Ok(a),
+            Err(e) => {
+                answer = Err(format!("{e:#}"));
+                break;
+            }
+        }
+    }
+
+    let memos = day.memos.iter().map(|(name, memo)| (*name, memo().entries())).collect();
+
+    // recorded answers only hold for the puzzle's own constants
+    let expected = match params.is_empty() {
+        true => expected,
+        false => None,
+    };
+
+    Ok(PartRun {
+        year: day.year,
+        day: day.day,
+        part: variant.part,
+        variant: variant.name,
+        input: source.label(),
+        answer,
+        expected,
+        times,
+        cpu_times,
+        phases,
+        alloc: alloc_stats,
+        params,
+        memos,
+    })
+}
+
+fn failed_run(day: &Day, variant: &Variant, source: &Source, error: String) -> PartRun {
+    PartRun {
+        year: day.year,
+        day: day.day,
+        part: variant.part,
+        variant: variant.name,
+        input: source.label(),
+        answer: Err(error),
+        expected: None,
+        times: Vec::new(),
+        cpu_times: Vec::new(),
+        phases: Vec::new(),
+        alloc: None,
+        params: Vec::new(),
+        memos: Vec::new(),
+    }
+}
+
+pub fn run_days<'a>(
+    days: impl IntoIterator<Item = &'a Day>,
+    parts: &[usize],
+    iterations: usize,
+    parallel: bool,
+    source: &Source,
+) -> Result<Report> {
+    let (concurrent, exclusive): (Vec<&Day>, Vec<&Day>) = match parallel {
+        true => days.into_iter().partition(|day| !day.uses_rayon),
+        false => (Vec::new(), days.into_iter().collect()),
+    };
+
+    let runs = Mutex::new(Vec::new());
+
+    // days that don't use rayon themselves share the pool, each one on a single
+    // thread, so the thread's cpu time belongs to that day alone
+    concurrent.into_par_iter().try_for_each(|day| {
+        let day_runs = run_day(day, parts, iterations, source, CpuClock::Thread)?;
+        runs.lock().unwrap().extend(day_runs);
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    let mut runs = runs.into_inner().unwrap();
+
+    for day in exclusive {
+        runs.extend(run_day(day, parts, iterations, source, CpuClock::Process)?);
+    }
+
+    runs.sort_by_key(|run| (run.year, run.day, run.part));
+
+    Ok(Report {
+        runs,
+        threads: rayon::current_num_threads(),
+    })
+}
+
+fn run_day(
+    day: &Day,
+    parts: &[usize],
+    iterations: usize,
+    source: &Source,
+    clock: CpuClock,
+) -> Result<Vec<PartRun>> {
+    let input = load_input_file(day.year, day.day, source)
+        .with_context(|| format!("could not load input for day {}", day.day));
+
+    let mut runs = Vec::new();
+
+    for &part in parts {
+        let variant = day.part(part)?;
+
+        // a missing input only fails this day's parts, the others still run
+        let run = match &input {
+            Ok(input) => run_part(day, variant, source, input.clone(), iterations, clock)?,
+            Err(e) => failed_run(day, variant, source, format!("{e:#}")),
+        };
+
+        Report::print_run(&run);
+        runs.push(run);
+    }
+
+    Ok(runs)
+}
+
+// runs every variant of a part on the same input
+pub fn compare(day: &Day, part: usize, source: &Source, input: String, iterations: usize) -> Result<Report> {
+    let runs = day
+        .variants_of(part)
+        .map(|variant| run_part(day, variant, source, input.clone(), iterations, CpuClock::Process))
+        .collect::<Result<Vec<_>>>()?;
+
+    if runs.is_empty() {
+        anyhow::bail!("'{part}' is not a valid part");
+    }
+
+    Ok(Report {
+        runs,
+        threads: rayon::current_num_threads(),
+    })
+}
+
+// runs parts on every account's cached input, never downloading missing ones
+pub fn verify<'a>(days: impl IntoIterator<Item = &'a Day>, parts: &[usize], sources: &[Source]) -> Result<Report> {
+    let mut runs = Vec::new();
+
+    for day in days {
+        for source in sources {
+            let path = input_path(day.year, day.day, source);
+
+            if !path.exists() {
+                log::debug!("skipping '{}', input is not cached", path.display());
+                continue;
+            }
+
+            let input = load_input_file(day.year, day.day, source)?;
+
+            for &part in parts {
+                let run = run_part(day, day.part(part)?, source, input.clone(), 1, CpuClock::Process)?;
+                Report::print_run(&run);
+                runs.push(run);
+            }
+        }
+    }
+
+    Ok(Report {
+        runs,
+        threads: rayon::current_num_threads(),
+    })
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() < 5 {
+        format!("{:.3} millis", duration.as_micros() as f64 / 1000.0)
+    } else {
+        format!("{:.3} seconds", duration.as_secs_f64())
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
+
+use anyhow::{Context, Result};
+
+static OVERRIDES: OnceLock<HashMap<String, String>> = OnceLock::new();
+static REQUESTED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static USED: RefCell<Option<Vec<(&'static str, String)>>> = const { RefCell::new(None) };
+}
+
+// parses `name=value` pairs, either repeated or separated by commas
+pub fn set_overrides(values: &[String]) -> Result<()> {
+    let mut overrides = HashMap::new();
+
+    for value in values.iter().flat_map(|v| v.split(',')) {
+        let (name, value) = value
+            .split_once('=')
+            .with_context(|| format!("parameter '{value}' is not of the form 'name=value'"))?;
+
+        overrides.insert(name.trim().to_string(), value.trim().to_string());
+    }
+
+    OVERRIDES
+        .set(overrides)
+        .map_err(|_| anyhow::anyhow!("parameters were already set"))
+}
+
+// a puzzle constant that can be changed with `--param name=value`
+pub fn param<T>(name: &'static str, default: T) -> Result<T>
+where
+    T: FromStr + Display,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    {
+        let mut requested = REQUESTED.lock().unwrap();
+        if !requested.contains(&name) {
+            requested.push(name);
+        }
+    }
+
+    let Some(value) = OVERRIDES.get().and_then(|o| o.get(name))
+    else { return Ok(default) };
+
+    let value: T = value
+        .parse()
+        .with_context(|| format!("parameter '{name}' has invalid value '{value}'"))?;
+
+    USED.with_borrow_mut(|used| {
+        if let Some(used) = used {
+            used.push((name, value.to_string()));
+        }
+    });
+
+    Ok(value)
+}
+
+// runs `f` and returns the overridden parameters it read on this thread
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<(&'static str, String)>) {
+    let previous = USED.replace(Some(Vec::new()));
+    let result = f();
+    let used = USED.replace(previous).unwrap();
+
+    (result, used)
+}
+
+pub fn unused_overrides() -> Vec<String> {
+    let requested: HashSet<&str> = REQUESTED.lock().unwrap().iter().copied().collect();
+
+    let mut unused: Vec<String> = OVERRIDES
+        .get()
+        .into_iter()
+        .flat_map(|o| o.keys())
+        .filter(|name| !requested.contains(name.as_str()))
+        .cloned()
+        .collect();
+    unused.sort();
+
+    unused
+}
@@ -0,0 +1,89 @@
+use std::{
+    cell::RefCell,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+#[derive(Clone, Debug)]
+pub struct Span {
+    pub name: &'static str,
+    pub depth: usize,
+    // offset from the start of the process, so spans of different parts line up
+    pub start: Duration,
+    pub duration: Duration,
+    pub thread: usize,
+}
+
+struct Collector {
+    spans: Vec<Span>,
+    depth: usize,
+}
+
+thread_local! {
+    static COLLECTOR: RefCell<Option<Collector>> = const { RefCell::new(None) };
+}
+
+pub struct Phase {
+    index: Option<usize>,
+    start: Instant,
+}
+
+// times everything until the returned guard is dropped; phases opened on
+// threads the runner isn't collecting on (e.g. rayon workers) are ignored
+pub fn phase(name: &'static str) -> Phase {
+    let start = Instant::now();
+
+    let index = COLLECTOR.with_borrow_mut(|collector| {
+        let collector = collector.as_mut()?;
+
+        collector.spans.push(Span {
+            name,
+            depth: collector.depth,
+            start: start - *EPOCH,
+            duration: Duration::ZERO,
+            thread: rayon::current_thread_index().map_or(0, |i| i + 1),
+        });
+        collector.depth += 1;
+
+        Some(collector.spans.len() - 1)
+    });
+
+    Phase { index, start }
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        let Some(index) = self.index
+        else { return };
+
+        let duration = self.start.elapsed();
+
+        COLLECTOR.with_borrow_mut(|collector| {
+            if let Some(collector) = collector {
+                collector.spans[index].duration = duration;
+                collector.depth -= 1;
+            }
+        });
+    }
+}
+
+// runs `f` inside a root span and returns every span opened on this thread
+pub fn collect<T>(name: &'static str, f: impl FnOnce() -> T) -> (T, Vec<Span>) {
+    LazyLock::force(&EPOCH);
+
+    let previous = COLLECTOR.replace(Some(Collector {
+        spans: Vec::new(),
+        depth: 0,
+    }));
+
+    let result = {
+        let _root = phase(name);
+        f()
+    };
+
+    let collector = COLLECTOR.replace(previous).unwrap();
+
+    (result, collector.spans)
+}
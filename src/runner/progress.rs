@@ -0,0 +1,119 @@
+use std::{
+    cell::RefCell,
+    io::{IsTerminal, Write},
+    panic::AssertUnwindSafe,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use super::{alloc, format_duration};
+
+#[derive(Default)]
+struct State {
+    total: AtomicUsize,
+    done: AtomicUsize,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<State>>> = const { RefCell::new(None) };
+}
+
+// cheap to clone and safe to share with rayon workers; does nothing when the
+// part isn't being run by the runner
+#[derive(Clone, Default)]
+pub struct Progress {
+    state: Option<Arc<State>>,
+}
+
+impl Progress {
+    pub fn set_total(&self, total: usize) {
+        if let Some(state) = &self.state {
+            state.total.store(total, Ordering::Relaxed);
+        }
+    }
+
+    pub fn inc(&self, n: usize) {
+        if let Some(state) = &self.state {
+            state.done.fetch_add(n, Ordering::Relaxed);
+        }
+    }
+}
+
+// must be called on the thread the runner started the part on
+pub fn progress() -> Progress {
+    Progress {
+        state: CURRENT.with_borrow(|state| state.clone()),
+    }
+}
+
+// runs `f`, drawing a progress bar on stderr while it reports progress
+pub fn track<T>(draw: bool, f: impl FnOnce() -> T) -> T {
+    let state = Arc::new(State::default());
+    let previous = CURRENT.replace(Some(state.clone()));
+
+    let draw = draw && std::io::stderr().is_terminal();
+    let done = AtomicBool::new(false);
+
+    let result = std::thread::scope(|s| {
+        let drawer = draw.then(|| s.spawn(|| alloc::untracked(|| draw_until(&state, &done))));
+
+        // a panicking part must still stop the drawer, or the scope never ends
+        let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+
+        done.store(true, Ordering::Relaxed);
+        if let Some(drawer) = drawer {
+            drawer.thread().unpark();
+        }
+
+        result
+    });
+
+    CURRENT.set(previous);
+
+    result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+fn draw_until(state: &State, done: &AtomicBool) {
+    const WIDTH: usize = 30;
+
+    let start = Instant::now();
+    let mut drawn = false;
+
+    while !done.load(Ordering::Relaxed) {
+        std::thread::park_timeout(Duration::from_millis(100));
+
+        let total = state.total.load(Ordering::Relaxed);
+        let finished = state.done.load(Ordering::Relaxed).min(total);
+
+        // quick parts finish before the bar would be worth drawing
+        if total == 0 || done.load(Ordering::Relaxed) || start.elapsed() < Duration::from_millis(250) {
+            continue;
+        }
+
+        let fraction = finished as f64 / total as f64;
+        let filled = (fraction * WIDTH as f64) as usize;
+
+        let eta = match finished {
+            0 => "?".to_string(),
+            _ => format_duration(start.elapsed().mul_f64((1.0 - fraction) / fraction)),
+        };
+
+        eprint!(
+            "\r\x1b[2K[{}{}] {:>3.0}% {finished}/{total} eta {eta}",
+            "#".repeat(filled),
+            " ".repeat(WIDTH - filled),
+            fraction * 100.0,
+        );
+        let _ = std::io::stderr().flush();
+
+        drawn = true;
+    }
+
+    if drawn {
+        eprint!("\r\x1b[2K");
+        let _ = std::io::stderr().flush();
+    }
+}
@@ -0,0 +1,358 @@
+use std::{fmt::Write, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+
+use crate::util::Json;
+
+use super::{PartRun, alloc::format_bytes, format_duration};
+
+pub struct Report {
+    pub runs: Vec<PartRun>,
+    pub threads: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Format::Json),
+            Some("csv") => Ok(Format::Csv),
+            Some("md") => Ok(Format::Markdown),
+            _ => Err(anyhow::anyhow!(
+                "cannot infer report format of '{}', expected .json, .csv or .md",
+                path.display()
+            )),
+        }
+    }
+}
+
+impl Report {
+    pub fn terminal_line(run: &PartRun) -> String {
+        let answer = match &run.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => e.lines().next().unwrap_or_default().to_string(),
+        };
+
+        let time = match run.times.len() {
+            0 | 1 => format!(
+                "{} (cpu {})",
+                format_duration(run.median()),
+                format_duration(run.cpu_median()),
+            ),
+            n => format!(
+                "{} (cpu {}, min {}, max {}, {n} runs)",
+                format_duration(run.median()),
+                format_duration(run.cpu_median()),
+                format_duration(run.min()),
+                format_duration(run.max()),
+            ),
+        };
+
+        let mut line = format!(
+            "y{} day {:>2} part {}{}{}  {answer:<20} {:<8} {time}",
+            run.year,
+            run.day,
+            run.part,
+            variant_label(run),
+            input_label(run),
+            run.status().name(),
+        );
+
+        if !run.params.is_empty() {
+            let params: Vec<String> = run
+                .params
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect();
+
+            write!(line, " {{{}}}", params.join(", ")).unwrap();
+        }
+
+        if !run.memos.is_empty() {
+            let memos: Vec<String> = run
+                .memos
+                .iter()
+                .map(|(name, len)| format!("{name} {len} entries"))
+                .collect();
+
+            write!(line, " <{}>", memos.join(", ")).unwrap();
+        }
+
+        if let Some(alloc) = run.alloc {
+            write!(
+                line,
+                " [{} in {} allocs, peak {}]",
+                format_bytes(alloc.bytes),
+                alloc.count,
+                format_bytes(alloc.peak),
+            )
+            .unwrap();
+        }
+
+        line
+    }
+
+    pub fn phase_lines(run: &PartRun) -> Vec<String> {
+        run.phases
+            .iter()
+            .skip(1)
+            .map(|span| {
+                let indent = "  ".repeat(span.depth + 1);
+                let name_width = 24usize.saturating_sub(indent.len());
+
+                format!(
+                    "{indent}{:<name_width$} {}",
+                    span.name,
+                    format_duration(span.duration),
+                )
+            })
+            .collect()
+    }
+
+    pub fn print_run(run: &PartRun) {
+        let mut out = Self::terminal_line(run);
+
+        for line in Self::phase_lines(run) {
+            out.push('\n');
+            out.push_str(&line);
+        }
+
+        println!("{out}");
+    }
+
+    // answers and timings of variants of the same part, relative to the first
+    pub fn print_comparison(&self) -> Result<()> {
+        let Some(base) = self.runs.first()
+        else { return Ok(()) };
+
+        println!("y{} day {} part {}", base.year, base.day, base.part);
+
+        for run in &self.runs {
+            let answer = match &run.answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {}", e.lines().next().unwrap_or_default()),
+            };
+
+            let speedup = base.median().as_secs_f64() / run.median().as_secs_f64().max(f64::EPSILON);
+
+            println!(
+                "  {:<16} {answer:<20} {:<8} {:>16} {speedup:>8.2}x",
+                run.variant,
+                run.status().name(),
+                format_duration(run.median()),
+            );
+        }
+
+        let answers: Vec<_> = self.runs.iter().map(|run| &run.answer).collect();
+
+        if answers.iter().any(|a| a.is_err()) {
+            anyhow::bail!("some variants failed");
+        }
+
+        if answers.windows(2).any(|w| w[0] != w[1]) {
+            anyhow::bail!("variants disagree on the answer");
+        }
+
+        println!("all {} variants agree", self.runs.len());
+
+        Ok(())
+    }
+
+    pub fn print_summary(&self) {
+        let stars: usize = self.runs.iter().map(PartRun::stars).sum();
+        let total: Duration = self.runs.iter().map(PartRun::median).sum();
+
+        println!(
+            "{} parts, {stars} stars, total {} on {} threads",
+            self.runs.len(),
+            format_duration(total),
+            self.threads,
+        );
+    }
+
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let contents = self.render(Format::from_path(path)?);
+
+        std::fs::write(path, contents)
+            .with_context(|| format!("could not write report to '{}'", path.display()))
+    }
+
+    pub fn export_trace(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_chrome_trace().to_string())
+            .with_context(|| format!("could not write trace to '{}'", path.display()))
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => self.to_json().to_string(),
+            Format::Csv => self.to_csv(),
+            Format::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let runs = self
+            .runs
+            .iter()
+            .map(|run| {
+                Json::object([
+                    ("year", run.year.into()),
+                    ("day", run.day.into()),
+                    ("part", run.part.into()),
+                    ("variant", run.variant.into()),
+                    ("input", run.input.as_str().into()),
+                    ("answer", run.answer.as_ref().ok().map(|a| a.to_string()).into()),
+                    ("error", run.answer.as_ref().err().cloned().into()),
+                    ("expected", run.expected.as_ref().map(|a| a.to_string()).into()),
+                    ("status", run.status().name().into()),
+                    ("stars", run.stars().into()),
+                    ("iterations", run.times.len().into()),
+                    ("median_ms", millis(run.median()).into()),
+                    ("cpu_median_ms", millis(run.cpu_median()).into()),
+                    ("min_ms", millis(run.min()).into()),
+                    ("max_ms", millis(run.max()).into()),
+                    ("alloc_bytes", run.alloc.map(|a| a.bytes).into()),
+                    ("alloc_count", run.alloc.map(|a| a.count).into()),
+                    ("peak_bytes", run.alloc.map(|a| a.peak).into()),
+                    (
+                        "params",
+                        Json::object(run.params.iter().map(|(k, v)| (*k, v.as_str().into()))),
+                    ),
+                    ("memos", Json::object(run.memos.iter().map(|(k, len)| (*k, (*len).into())))),
+                ])
+            })
+            .collect();
+
+        Json::object([
+            ("threads", self.threads.into()),
+            ("runs", Json::Array(runs)),
+        ])
+    }
+
+    // https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+    fn to_chrome_trace(&self) -> Json {
+        let mut events = Vec::new();
+
+        for run in &self.runs {
+            for span in &run.phases {
+                let name = match span.depth {
+                    0 => format!("y{} day {} {}", run.year, run.day, run.variant),
+                    _ => span.name.to_string(),
+                };
+
+                events.push(Json::object([
+                    ("name", name.into()),
+                    ("cat", if span.depth == 0 { "part" } else { "phase" }.into()),
+                    ("ph", "X".into()),
+                    ("ts", micros(span.start).into()),
+                    ("dur", micros(span.duration).into()),
+                    ("pid", 1usize.into()),
+                    ("tid", span.thread.into()),
+                ]));
+            }
+        }
+
+        Json::object([
+            ("traceEvents", Json::Array(events)),
+            ("displayTimeUnit", "ms".into()),
+        ])
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "year,day,part,variant,input,answer,expected,status,stars,iterations,median_ms,cpu_median_ms,min_ms,max_ms,alloc_bytes,alloc_count,peak_bytes\n",
+        );
+
+        for run in &self.runs {
+            let answer = run.answer.as_ref().map(|a| a.to_string()).unwrap_or_default();
+            let expected = run.expected.as_ref().map(|a| a.to_string()).unwrap_or_default();
+            let [alloc_bytes, alloc_count, peak_bytes] = match run.alloc {
+                Some(a) => [a.bytes, a.count, a.peak].map(|n| n.to_string()),
+                None => Default::default(),
+            };
+
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{:.3},{:.3},{:.3},{:.3},{},{},{}",
+                run.year,
+                run.day,
+                run.part,
+                run.variant,
+                csv_field(&run.input),
+                csv_field(&answer),
+                csv_field(&expected),
+                run.status().name(),
+                run.stars(),
+                run.times.len(),
+                millis(run.median()),
+                millis(run.cpu_median()),
+                millis(run.min()),
+                millis(run.max()),
+                alloc_bytes,
+                alloc_count,
+                peak_bytes,
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut md = String::from("| Day | Part | Status | Median | Stars |\n");
+        md.push_str("|----:|-----:|:-------|-------:|:-----:|\n");
+
+        for run in &self.runs {
+            writeln!(
+                md,
+                "| {}{} | {}{} | {} | {} | {} |",
+                run.day,
+                input_label(run),
+                run.part,
+                variant_label(run),
+                run.status().name(),
+                format_duration(run.median()),
+                "★".repeat(run.stars()),
+            )
+            .unwrap();
+        }
+
+        md
+    }
+}
+
+fn variant_label(run: &PartRun) -> String {
+    match run.variant == format!("part{}", run.part) {
+        true => String::new(),
+        false => format!(" ({})", run.variant),
+    }
+}
+
+fn input_label(run: &PartRun) -> String {
+    match run.input.is_empty() {
+        true => String::new(),
+        false => format!(" [{}]", run.input),
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
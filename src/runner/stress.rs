@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+};
+
+use anyhow::{Context, Result};
+
+use crate::util::Rng;
+
+use super::{Answer, Day, Variant, progress};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Agree,
+    Disagree,
+    // every variant failed, which usually means the input itself is invalid
+    AllFailed,
+    Failed(&'static str),
+}
+
+pub struct Failure {
+    pub case: usize,
+    pub input: String,
+    pub results: Vec<(&'static str, Result<Answer, String>)>,
+}
+
+// runs every variant of a part on generated inputs until they disagree or one
+// of them fails, then shrinks that input as far as it keeps failing the same way
+pub fn stress(day: &Day, part: usize, cases: usize, rng: &mut Rng) -> Result<Option<Failure>> {
+    let generate = day
+        .generator
+        .with_context(|| format!("day {} has no input generator", day.day))?;
+
+    let variants: Vec<&Variant> = day.variants_of(part).collect();
+
+    if variants.is_empty() {
+        anyhow::bail!("'{part}' is not a valid part");
+    }
+
+    if variants.len() == 1 {
+        log::warn!("part {part} of day {} has a single variant, only checking for failures", day.day);
+    }
+
+    // panics are expected here, their messages would bury the report
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let failure = progress::track(true, || {
+        let progress = progress::progress();
+        progress.set_total(cases);
+
+        for case in 0..cases {
+            let input = generate(rng);
+            let results = run_variants(&variants, &input);
+
+            let outcome = outcome(&results);
+
+            if matches!(outcome, Outcome::Disagree | Outcome::Failed(_)) {
+                let input = shrink(&variants, input, &outcome);
+                let results = run_variants(&variants, &input);
+                return Some(Failure { case, input, results });
+            }
+
+            progress.inc(1);
+        }
+
+        None
+    });
+
+    std::panic::set_hook(hook);
+
+    Ok(failure)
+}
+
+fn run_variants(variants: &[&Variant], input: &str) -> Vec<(&'static str, Result<Answer, String>)> {
+    variants
+        .iter()
+        .map(|variant| {
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| (variant.func)(input.to_string())));
+
+            let result = match result {
+                Ok(Ok(answer)) => Ok(answer),
+                Ok(Err(e)) => Err(format!("{e:?}")),
+                Err(panic) => Err(panic_message(panic)),
+            };
+
+            (variant.name, result)
+        })
+        .collect()
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    let message = match panic.downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => panic.downcast_ref::<String>().cloned().unwrap_or_default(),
+    };
+
+    format!("panicked: {message}")
+}
+
+fn outcome(results: &[(&'static str, Result<Answer, String>)]) -> Outcome {
+    if results.iter().all(|(_, r)| r.is_err()) {
+        return Outcome::AllFailed;
+    }
+
+    if let Some((name, _)) = results.iter().find(|(_, r)| r.is_err()) {
+        return Outcome::Failed(name);
+    }
+
+    match results.windows(2).all(|w| w[0].1 == w[1].1) {
+        true => Outcome::Agree,
+        false => Outcome::Disagree,
+    }
+}
+
+fn shrink(variants: &[&Variant], mut input: String, target: &Outcome) -> String {
+    'outer: loop {
+        for candidate in candidates(&input) {
+            if outcome(&run_variants(variants, &candidate)) == *target {
+                input = candidate;
+                continue 'outer;
+            }
+        }
+
+        return input;
+    }
+}
+
+// smaller versions of the input, biggest cuts first
+fn candidates(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let join = |lines: &[String]| lines.join("\n") + "\n";
+
+    let mut out = Vec::new();
+
+    if lines.len() > 1 {
+        for i in 0..lines.len() {
+            let mut lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+            lines.remove(i);
+            out.push(join(&lines));
+        }
+    }
+
+    let width = lines.first().map_or(0, |l| l.chars().count());
+    let rectangular = lines.len() > 1 && lines.iter().all(|l| l.chars().count() == width);
+
+    if rectangular && width > 1 {
+        for x in 0..width {
+            let lines: Vec<String> = lines
+                .iter()
+                .map(|l| l.chars().enumerate().filter(|(i, _)| *i != x).map(|(_, ch)| ch).collect())
+                .collect();
+            out.push(join(&lines));
+        }
+    }
+
+    for (y, line) in lines.iter().enumerate() {
+        let tokens: Vec<&str> = line.split(' ').collect();
+
+        let replace_line = |out: &mut Vec<String>, new: String| {
+            let mut lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+            lines[y] = new;
+            out.push(join(&lines));
+        };
+
+        if tokens.len() > 1 {
+            for i in 0..tokens.len() {
+                let mut tokens = tokens.clone();
+                tokens.remove(i);
+                replace_line(&mut out, tokens.join(" "));
+            }
+        }
+
+        for i in 0..tokens.len() {
+            let token = tokens[i].trim_end_matches(':');
+            let Ok(n) = token.parse::<u64>()
+            else { continue };
+
+            for smaller in [n / 2, n.saturating_sub(1)] {
+                if smaller < n {
+                    let mut tokens = tokens.clone();
+                    let new = tokens[i].replacen(token, &smaller.to_string(), 1);
+                    tokens[i] = &new;
+                    replace_line(&mut out, tokens.join(" "));
+                }
+            }
+        }
+
+        // single lines of digits like disk maps shrink one digit at a time
+        if !rectangular && !line.contains(' ') {
+            let chars: Vec<char> = line.chars().collect();
+
+            for i in 0..chars.len() {
+                let mut removed = chars.clone();
+                removed.remove(i);
+                replace_line(&mut out, removed.into_iter().collect());
+
+                if let Some(d) = chars[i].to_digit(10).filter(|d| *d > 0) {
+                    let mut decremented = chars.clone();
+                    decremented[i] = char::from_digit(d - 1, 10).unwrap();
+                    replace_line(&mut out, decremented.into_iter().collect());
+                }
+            }
+        }
+    }
+
+    // grids get simpler by turning cells into the most common cell
+    if rectangular {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for ch in input.chars().filter(|ch| *ch != '\n') {
+            *counts.entry(ch).or_default() += 1;
+        }
+
+        if let Some((&background, _)) = counts.iter().max_by_key(|(ch, count)| (**count, **ch)) {
+            for (i, ch) in input.char_indices() {
+                if ch != '\n' && ch != background {
+                    let mut candidate = input.to_string();
+                    candidate.replace_range(i..i + ch.len_utf8(), &background.to_string());
+                    out.push(candidate);
+                }
+            }
+        }
+    }
+
+    out.retain(|candidate| candidate != input);
+    out
+}
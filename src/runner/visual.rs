@@ -0,0 +1,222 @@
+use std::{
+    io::{IsTerminal, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::util::{
+    CellIndex, Grid,
+    terminal::{self, BLUE, CYAN, GREEN, MAGENTA, RED, RESET, YELLOW},
+};
+
+// draws a day's solution as it runs, frame by frame
+pub type VisualizeFn = fn(String, &mut Frames) -> Result<()>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => RED,
+            Color::Green => GREEN,
+            Color::Yellow => YELLOW,
+            Color::Blue => BLUE,
+            Color::Magenta => MAGENTA,
+            Color::Cyan => CYAN,
+        }
+    }
+}
+
+// a snapshot of a grid, with some cells coloured or drawn as another character
+#[derive(Clone)]
+pub struct Frame {
+    grid: Grid<char>,
+    colors: Grid<Option<Color>>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    pub fn new(grid: Grid<char>) -> Self {
+        let colors = grid.clone().map(|_| None);
+
+        Frame { grid, colors, caption: None }
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    // cells outside of the grid are left out
+    pub fn paint(mut self, cells: impl IntoIterator<Item = CellIndex>, color: Color) -> Self {
+        for cell in cells {
+            if let Some(slot) = self.colors.get_mut(cell) {
+                *slot = Some(color);
+            }
+        }
+
+        self
+    }
+
+    pub fn mark(mut self, cell: CellIndex, ch: char, color: Color) -> Self {
+        if let Some(slot) = self.grid.get_mut(cell) {
+            *slot = ch;
+        }
+
+        self.paint([cell], color)
+    }
+
+    fn render(&self, styled: bool, max_rows: usize) -> Vec<String> {
+        let mut lines: Vec<String> = self.caption.iter().cloned().collect();
+
+        for y in 0..self.grid.height().min(max_rows.try_into().unwrap_or(isize::MAX)) {
+            let mut line = String::new();
+            let mut current = None;
+
+            for x in 0..self.grid.width() {
+                let color = self.colors.get([x, y]).copied().flatten().filter(|_| styled);
+
+                if color != current {
+                    line.push_str(color.map_or(RESET, Color::code));
+                    current = color;
+                }
+
+                line.push(*self.grid.get([x, y]).unwrap());
+            }
+
+            if current.is_some() {
+                line.push_str(RESET);
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+}
+
+impl From<Grid<char>> for Frame {
+    fn from(grid: Grid<char>) -> Self {
+        Frame::new(grid)
+    }
+}
+
+impl From<&Grid<char>> for Frame {
+    fn from(grid: &Grid<char>) -> Self {
+        Frame::new(grid.clone())
+    }
+}
+
+enum Output {
+    Terminal { delay: Duration, last: Option<Instant> },
+    Directory(PathBuf),
+}
+
+// where a visualisation's frames go, played back in the terminal or written
+// to a directory as numbered text files
+pub struct Frames {
+    output: Output,
+    count: usize,
+}
+
+impl Frames {
+    pub fn terminal(fps: f64) -> Result<Self> {
+        if !(fps > 0.0 && fps.is_finite()) {
+            anyhow::bail!("frame rate must be above 0, got {fps}");
+        }
+
+        let delay = Duration::from_secs_f64(1.0 / fps);
+
+        Ok(Frames {
+            output: Output::Terminal { delay, last: None },
+            count: 0,
+        })
+    }
+
+    pub fn directory(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("could not create directory '{}'", path.display()))?;
+
+        Ok(Frames {
+            output: Output::Directory(path),
+            count: 0,
+        })
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn push(&mut self, frame: impl Into<Frame>) -> Result<()> {
+        let frame = frame.into();
+        self.count += 1;
+
+        match &mut self.output {
+            Output::Terminal { delay, last } => {
+                let styled = std::io::stdout().is_terminal();
+
+                // taller grids are cut off so each frame draws over the last,
+                // leaving room for the caption and the prompt afterwards
+                let max_rows = match styled {
+                    true => terminal::height().saturating_sub(2),
+                    false => usize::MAX,
+                };
+
+                let lines = frame.render(styled, max_rows);
+
+                if let Some(last) = last {
+                    thread::sleep(delay.saturating_sub(last.elapsed()));
+                }
+
+                *last = Some(Instant::now());
+
+                let mut out = String::new();
+
+                match styled {
+                    true => out.push_str("\x1b[H"),
+                    false if self.count > 1 => out.push('\n'),
+                    false => {}
+                }
+
+                for line in lines {
+                    out.push_str(&line);
+
+                    if styled {
+                        out.push_str("\x1b[K");
+                    }
+
+                    out.push('\n');
+                }
+
+                if styled {
+                    out.push_str("\x1b[J");
+                }
+
+                let mut stdout = std::io::stdout().lock();
+                stdout.write_all(out.as_bytes())?;
+                stdout.flush().context("could not draw frame")
+            }
+            Output::Directory(dir) => {
+                let path = dir.join(format!("{:05}.txt", self.count));
+                let text = frame.render(false, usize::MAX).join("\n") + "\n";
+
+                std::fs::write(&path, text).with_context(|| format!("could not write frame '{}'", path.display()))
+            }
+        }
+    }
+}
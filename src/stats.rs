@@ -0,0 +1,188 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use crate::input::Source;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Fetched,
+    Solved(usize),
+}
+
+// unix timestamps of when a puzzle was first fetched and each part solved
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Times {
+    pub fetched: Option<u64>,
+    pub solved: [Option<u64>; 2],
+}
+
+impl Times {
+    // how long a part took since the puzzle was fetched
+    pub fn to_solve(&self, part: usize) -> Option<u64> {
+        Some(self.solved[part - 1]?.saturating_sub(self.fetched?))
+    }
+
+    pub fn part2_delta(&self) -> Option<u64> {
+        Some(self.solved[1]?.saturating_sub(self.solved[0]?))
+    }
+}
+
+// kept next to the account's inputs as lines of `fetched: <time>` and `<part>: <time>`
+pub fn times_path(year: usize, day: usize, user: Option<&str>) -> PathBuf {
+    Source::new(user.map(str::to_string), "").dir(year).join(format!("{day}.times"))
+}
+
+pub fn load_times(year: usize, day: usize, user: Option<&str>) -> Result<Times> {
+    let path = times_path(year, day, user);
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Times::default()),
+        Err(e) => return Err(e).with_context(|| format!("could not read times '{}'", path.display())),
+    };
+
+    let mut times = Times::default();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (event, time) = line
+            .split_once(':')
+            .with_context(|| format!("{}:{}: expected '<event>: <time>'", path.display(), i + 1))?;
+
+        let time: u64 = time
+            .trim()
+            .parse()
+            .with_context(|| format!("{}:{}: time is not a number", path.display(), i + 1))?;
+
+        match event.trim() {
+            "fetched" => times.fetched = Some(time),
+            "1" => times.solved[0] = Some(time),
+            "2" => times.solved[1] = Some(time),
+            event => anyhow::bail!("{}:{}: unknown event '{event}'", path.display(), i + 1),
+        }
+    }
+
+    Ok(times)
+}
+
+// only the first time of an event counts, refetching doesn't restart the clock
+pub fn record(year: usize, day: usize, user: Option<&str>, event: Event) -> Result<()> {
+    let mut times = load_times(year, day, user)?;
+
+    let slot = match event {
+        Event::Fetched => &mut times.fetched,
+        Event::Solved(part) => &mut times.solved[part - 1],
+    };
+
+    if slot.is_some() {
+        return Ok(());
+    }
+
+    *slot = Some(now());
+
+    let mut text = String::new();
+
+    if let Some(time) = times.fetched {
+        text.push_str(&format!("fetched: {time}\n"));
+    }
+
+    for (part, time) in times.solved.iter().enumerate() {
+        if let Some(time) = time {
+            text.push_str(&format!("{}: {time}\n", part + 1));
+        }
+    }
+
+    let path = times_path(year, day, user);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+
+    std::fs::write(&path, text).with_context(|| format!("could not write times '{}'", path.display()))
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn print_stats(year: usize, days: &[usize], user: Option<&str>) -> Result<()> {
+    println!("y{year} solve times{}", user.map(|u| format!(" of {u}")).unwrap_or_default());
+    println!(
+        "{:>4}  {:<16}  {:>10}  {:>10}  {:>10}",
+        "Day", "Fetched (UTC)", "Part 1", "Part 2", "Delta",
+    );
+
+    let mut solved = [Vec::new(), Vec::new()];
+
+    for &day in days {
+        let times = load_times(year, day, user)?;
+
+        let Some(fetched) = times.fetched
+        else { continue };
+
+        let elapsed = |time: Option<u64>| time.map_or("-".to_string(), format_elapsed);
+
+        println!(
+            "{day:>4}  {}  {:>10}  {:>10}  {:>10}",
+            format_date(fetched),
+            elapsed(times.to_solve(1)),
+            elapsed(times.to_solve(2)),
+            elapsed(times.part2_delta()),
+        );
+
+        for part in [1, 2] {
+            solved[part - 1].extend(times.to_solve(part));
+        }
+    }
+
+    for (part, times) in solved.iter_mut().enumerate() {
+        if times.is_empty() {
+            continue;
+        }
+
+        times.sort();
+
+        println!(
+            "part {}: {} solved, median {}, best {}",
+            part + 1,
+            times.len(),
+            format_elapsed(times[times.len() / 2]),
+            format_elapsed(times[0]),
+        );
+    }
+
+    Ok(())
+}
+
+pub fn format_elapsed(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn format_date(secs: u64) -> String {
+    let days = (secs / 86400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+    )
+}
@@ -0,0 +1,309 @@
+use anyhow::Result;
+
+// ChaCha20-Poly1305 as described in https://www.rfc-editor.org/rfc/rfc8439
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn words<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    std::array::from_fn(|i| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap()))
+}
+
+fn block(key: &[u8; KEY_LEN], counter: u32, nonce: &[u8; NONCE_LEN]) -> [u8; 64] {
+    let key: [u32; 8] = words(key);
+    let nonce: [u32; 3] = words(nonce);
+
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574]);
+    state[4..12].copy_from_slice(&key);
+    state[12] = counter;
+    state[13..].copy_from_slice(&nonce);
+
+    let mut working = state;
+
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut out = [0; 64];
+
+    for (i, (w, s)) in working.iter().zip(state).enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&w.wrapping_add(s).to_le_bytes());
+    }
+
+    out
+}
+
+fn chacha20(key: &[u8; KEY_LEN], counter: u32, nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(64).enumerate() {
+        let stream = block(key, counter.wrapping_add(i as u32), nonce);
+
+        for (byte, key) in chunk.iter_mut().zip(stream) {
+            *byte ^= key;
+        }
+    }
+}
+
+// the message is processed in 16 byte blocks as numbers below 2^130 - 5,
+// kept in five 26 bit limbs so products fit in a u64
+fn poly1305(key: &[u8; 32], message: &[u8]) -> [u8; TAG_LEN] {
+    let le32 = |i: usize| u32::from_le_bytes(key[i..i + 4].try_into().unwrap());
+
+    let r = [
+        le32(0) & 0x03ff_ffff,
+        (le32(3) >> 2) & 0x03ff_ff03,
+        (le32(6) >> 4) & 0x03ff_c0ff,
+        (le32(9) >> 6) & 0x03f0_3fff,
+        (le32(12) >> 8) & 0x000f_ffff,
+    ]
+    .map(u64::from);
+
+    let s = [1, 2, 3, 4].map(|i| r[i] * 5);
+
+    let mut h = [0u64; 5];
+
+    for chunk in message.chunks(16) {
+        let mut block = [0u8; 17];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block[chunk.len()] = 1;
+
+        let le = |i: usize| u64::from(u32::from_le_bytes(block[i..i + 4].try_into().unwrap()));
+
+        h[0] += le(0) & 0x03ff_ffff;
+        h[1] += (le(3) >> 2) & 0x03ff_ffff;
+        h[2] += (le(6) >> 4) & 0x03ff_ffff;
+        h[3] += (le(9) >> 6) & 0x03ff_ffff;
+        h[4] += (le(12) >> 8) | (u64::from(block[16]) << 24);
+
+        let d = [
+            h[0] * r[0] + h[1] * s[3] + h[2] * s[2] + h[3] * s[1] + h[4] * s[0],
+            h[0] * r[1] + h[1] * r[0] + h[2] * s[3] + h[3] * s[2] + h[4] * s[1],
+            h[0] * r[2] + h[1] * r[1] + h[2] * r[0] + h[3] * s[3] + h[4] * s[2],
+            h[0] * r[3] + h[1] * r[2] + h[2] * r[1] + h[3] * r[0] + h[4] * s[3],
+            h[0] * r[4] + h[1] * r[3] + h[2] * r[2] + h[3] * r[1] + h[4] * r[0],
+        ];
+
+        let mut carry = 0;
+
+        for i in 0..5 {
+            let value = d[i] + carry;
+            h[i] = value & 0x03ff_ffff;
+            carry = value >> 26;
+        }
+
+        h[0] += carry * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= 0x03ff_ffff;
+    }
+
+    // fully carry, then subtract p if h is at least p
+    let mut carry = 0;
+
+    for i in [1, 2, 3, 4, 0] {
+        h[i] += carry;
+        carry = h[i] >> 26;
+        h[i] &= 0x03ff_ffff;
+
+        if i == 4 {
+            carry *= 5;
+        }
+    }
+
+    h[1] += carry;
+
+    let mut g = [0u64; 5];
+    let mut carry = 5;
+
+    for i in 0..5 {
+        let value = h[i] + carry;
+        g[i] = value & 0x03ff_ffff;
+        carry = value >> 26;
+    }
+
+    // a carry out of g = h + 5 means h + 5 reached 2^130, so h - p is g
+    if carry != 0 {
+        h = g;
+    }
+
+    let h = [0, 26, 52, 78, 104]
+        .iter()
+        .zip(h)
+        .fold(0u128, |acc, (shift, limb)| acc.wrapping_add(u128::from(limb) << shift));
+    let pad = u128::from_le_bytes(key[16..].try_into().unwrap());
+
+    h.wrapping_add(pad).to_le_bytes()
+}
+
+fn tag(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+    let otk: [u8; 32] = block(key, 0, nonce)[..32].try_into().unwrap();
+
+    let pad = |data: &mut Vec<u8>| data.resize(data.len().next_multiple_of(16), 0);
+
+    let mut message = aad.to_vec();
+    pad(&mut message);
+    message.extend_from_slice(ciphertext);
+    pad(&mut message);
+    message.extend_from_slice(&(aad.len() as u64).to_le_bytes());
+    message.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+
+    poly1305(&otk, &message)
+}
+
+// the ciphertext followed by its tag
+pub fn seal(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut out = plaintext.to_vec();
+    chacha20(key, 1, nonce, &mut out);
+
+    let tag = tag(key, nonce, aad, &out);
+    out.extend_from_slice(&tag);
+    out
+}
+
+pub fn open(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < TAG_LEN {
+        anyhow::bail!("ciphertext is too short");
+    }
+
+    let (ciphertext, expected) = sealed.split_at(sealed.len() - TAG_LEN);
+    let tag = tag(key, nonce, aad, ciphertext);
+
+    // compared without an early exit so timing doesn't leak the tag
+    let diff = tag.iter().zip(expected).fold(0, |acc, (a, b)| acc | (a ^ b));
+
+    if diff != 0 {
+        anyhow::bail!("authentication failed, wrong key or corrupted file");
+    }
+
+    let mut plaintext = ciphertext.to_vec();
+    chacha20(key, 1, nonce, &mut plaintext);
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        let digits: Vec<u8> = text.bytes().filter(u8::is_ascii_hexdigit).collect();
+
+        digits
+            .chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect()
+    }
+
+    // the key, nonce and plaintext of section 2.8.2
+    fn aead_vector() -> ([u8; KEY_LEN], [u8; NONCE_LEN], Vec<u8>, Vec<u8>) {
+        let key = std::array::from_fn(|i| 0x80 + i as u8);
+        let nonce = hex("07000000 40414243 44454647").try_into().unwrap();
+        let aad = hex("50515253 c0c1c2c3 c4c5c6c7");
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip \
+            for the future, sunscreen would be it."
+            .to_vec();
+
+        (key, nonce, aad, plaintext)
+    }
+
+    // section 2.2.1
+    #[test]
+    fn quarter_round_on_state() {
+        let mut state = [
+            0x879531e0, 0xc5ecf37d, 0x516461b1, 0xc9a62f8a,
+            0x44c20ef3, 0x3390af7f, 0xd9fc690b, 0x2a5f714c,
+            0x53372767, 0xb00a5631, 0x974c541a, 0x359e9963,
+            0x5c971061, 0x3d631689, 0x2098d9d6, 0x91dbd320,
+        ];
+
+        quarter_round(&mut state, 2, 7, 8, 13);
+
+        assert_eq!(state, [
+            0x879531e0, 0xc5ecf37d, 0xbdb886dc, 0xc9a62f8a,
+            0x44c20ef3, 0x3390af7f, 0xd9fc690b, 0xcfacafd2,
+            0xe46bea80, 0xb00a5631, 0x974c541a, 0x359e9963,
+            0x5c971061, 0xccc07c79, 0x2098d9d6, 0x91dbd320,
+        ]);
+    }
+
+    // section 2.3.2
+    #[test]
+    fn block_function() {
+        let key = std::array::from_fn(|i| i as u8);
+        let nonce = hex("00000009 0000004a 00000000").try_into().unwrap();
+
+        let expected = hex(
+            "10f1e7e4d13b5915500fdd1fa32071c4 c7d1f4c733c068030422aa9ac3d46c4e \
+             d2826446079faa0914c2d705d98b02a2 b5129cd1de164eb9cbd083e8a2503c4e",
+        );
+
+        assert_eq!(block(&key, 1, &nonce).to_vec(), expected);
+    }
+
+    // section 2.5.2
+    #[test]
+    fn poly1305_tag() {
+        let key = hex("85d6be7857556d337f4452fe42d506a8 0103808afb0db2fd4abff6af4149f51b");
+
+        assert_eq!(
+            poly1305(&key.try_into().unwrap(), b"Cryptographic Forum Research Group").to_vec(),
+            hex("a8061dc1305136c6c22b8baf0c0127a9"),
+        );
+    }
+
+    // section 2.8.2
+    #[test]
+    fn aead_seal_and_open() {
+        let (key, nonce, aad, plaintext) = aead_vector();
+
+        let expected = hex(
+            "d31a8d34648e60db7b86afbc53ef7ec2 a4aded51296e08fea9e2b5a736ee62d6 \
+             3dbea45e8ca9671282fafb69da92728b 1a71de0a9e060b2905d6a5b67ecd3b36 \
+             92ddbd7f2d778b8c9803aee328091b58 fab324e4fad675945585808b4831d7bc \
+             3ff4def08e4b7a9de576d26586cec64b 6116 \
+             1ae10b594f09e26a7e902ecbd0600691",
+        );
+
+        let sealed = seal(&key, &nonce, &aad, &plaintext);
+        assert_eq!(sealed, expected);
+
+        assert_eq!(open(&key, &nonce, &aad, &sealed).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn open_rejects_tampering() {
+        let (key, nonce, aad, plaintext) = aead_vector();
+        let sealed = seal(&key, &nonce, &aad, &plaintext);
+
+        for i in [0, plaintext.len() / 2, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert!(open(&key, &nonce, &aad, &tampered).is_err(), "byte {i} was flipped");
+        }
+
+        assert!(open(&key, &nonce, b"other aad", &sealed).is_err());
+
+        let mut wrong_key = key;
+        wrong_key[0] ^= 1;
+        assert!(open(&wrong_key, &nonce, &aad, &sealed).is_err());
+
+        assert!(open(&key, &nonce, &aad, &sealed[..TAG_LEN - 1]).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+// malformed puzzle input and where it went wrong, returned by days instead
+// of panicking; `Reader` and `Grid` fill in the location themselves
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputError {
+    pub section: Option<&'static str>,
+    // both start at 1
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub expected: String,
+    pub found: Option<String>,
+}
+
+impl InputError {
+    pub fn new(expected: impl Into<String>) -> Self {
+        InputError {
+            expected: expected.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn section(mut self, section: &'static str) -> Self {
+        self.section = Some(section);
+        self
+    }
+
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn found(mut self, found: impl Into<String>) -> Self {
+        self.found = Some(found.into());
+        self
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid input")?;
+
+        let location = [
+            self.section.map(|section| ("section", format!("'{section}'"))),
+            self.line.map(|line| ("line", line.to_string())),
+            self.column.map(|column| ("column", column.to_string())),
+        ];
+
+        for (i, (name, value)) in location.into_iter().flatten().enumerate() {
+            let separator = if i == 0 { " at" } else { "," };
+            write!(f, "{separator} {name} {value}")?;
+        }
+
+        write!(f, ": expected {}", self.expected)?;
+
+        if let Some(found) = &self.found {
+            write!(f, ", found {found}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for InputError {}
@@ -2,6 +2,8 @@ use std::{fmt::Display, ops::{Add, AddAssign, Deref, DerefMut, Sub, SubAssign}};
 
 use anyhow::{Context, Result};
 
+use super::diagnostic::InputError;
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct CellIndex {
@@ -129,27 +131,27 @@ impl From<(isize, isize)> for CellIndex {
     }
 }
 
-impl Into<[usize; 2]> for CellIndex {
-    fn into(self) -> [usize; 2] {
-        [self.x.try_into().unwrap(), self.y.try_into().unwrap()]
+impl From<CellIndex> for [usize; 2] {
+    fn from(i: CellIndex) -> Self {
+        [i.x.try_into().unwrap(), i.y.try_into().unwrap()]
     }
 }
 
-impl Into<[isize; 2]> for CellIndex {
-    fn into(self) -> [isize; 2] {
-        [self.x, self.y]
+impl From<CellIndex> for [isize; 2] {
+    fn from(i: CellIndex) -> Self {
+        [i.x, i.y]
     }
 }
 
-impl Into<(usize, usize)> for CellIndex {
-    fn into(self) -> (usize, usize) {
-        (self.x.try_into().unwrap(), self.y.try_into().unwrap())
+impl From<CellIndex> for (usize, usize) {
+    fn from(i: CellIndex) -> Self {
+        (i.x.try_into().unwrap(), i.y.try_into().unwrap())
     }
 }
 
-impl Into<(isize, isize)> for CellIndex {
-    fn into(self) -> (isize, isize) {
-        (self.x, self.y)
+impl From<CellIndex> for (isize, isize) {
+    fn from(i: CellIndex) -> Self {
+        (i.x, i.y)
     }
 }
 
@@ -227,7 +229,7 @@ impl<T> Grid<T> {
             && (0..self.height).contains(&i.y)
     }
 
-    pub fn iter(&self) -> GridIter<T> {
+    pub fn iter(&self) -> GridIter<'_, T> {
         self.into_iter()
     }
 
@@ -284,10 +286,12 @@ impl Grid<char> {
             match width {
                 Some(width) => {
                     if line.len() != width {
-                        anyhow::bail!(
-                            "invalid grid: line {height} has width of '{}' instead of '{width}'",
-                            line.len()
-                        );
+                        let error = InputError::new(format!("a row {width} wide like the first"))
+                            .line(height as usize + 1)
+                            .column(line.len().min(width) + 1)
+                            .found(format!("a row {} wide", line.len()));
+
+                        return Err(error.into());
                     }
                 }
                 None => width = Some(line.len()),
@@ -309,19 +313,19 @@ impl Grid<char> {
             height,
         })
     }
+}
 
-    pub fn to_string(&self) -> String {
-        let mut string = String::new();
-
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in 0..self.height() {
             for x in 0..self.width() {
                 let value = self.get([x, y]).unwrap();
-                string.push(*value);
+                write!(f, "{value}")?;
             }
-            string.push('\n');
+            writeln!(f)?;
         }
 
-        string
+        Ok(())
     }
 }
 
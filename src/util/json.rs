@@ -0,0 +1,395 @@
+use std::fmt::{Display, Write};
+
+use anyhow::{Context, Result};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K, I>(fields: I) -> Self
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = (K, Json)>,
+    {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser { text, pos: 0 };
+
+        let value = parser.value()?;
+        parser.whitespace();
+
+        if parser.pos != text.len() {
+            return Err(parser.error("end of input"));
+        }
+
+        Ok(value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_f64().filter(|n| *n >= 0.0 && n.fract() == 0.0).map(|n| n as u64)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Json::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, expected: &str) -> anyhow::Error {
+        let found = self.text[self.pos..].chars().next();
+        match found {
+            Some(ch) => anyhow::anyhow!("expected {expected} at byte {}, found '{ch}'", self.pos),
+            None => anyhow::anyhow!("expected {expected} at byte {}, found end of input", self.pos),
+        }
+    }
+
+    fn whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.whitespace();
+
+        match self.text[self.pos..].starts_with(token) {
+            true => {
+                self.pos += token.len();
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(&format!("'{token}'"))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.whitespace();
+
+        match self.text[self.pos..].chars().next() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::String),
+            Some('-' | '0'..='9') => self.number(),
+            _ if self.eat("null") => Ok(Json::Null),
+            _ if self.eat("true") => Ok(Json::Bool(true)),
+            _ if self.eat("false") => Ok(Json::Bool(false)),
+            _ => Err(self.error("a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json> {
+        self.expect("{")?;
+
+        let mut fields = Vec::new();
+
+        if self.eat("}") {
+            return Ok(Json::Object(fields));
+        }
+
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            self.expect(":")?;
+            fields.push((key, self.value()?));
+
+            if self.eat("}") {
+                return Ok(Json::Object(fields));
+            }
+
+            self.expect(",")?;
+        }
+    }
+
+    fn array(&mut self) -> Result<Json> {
+        self.expect("[")?;
+
+        let mut values = Vec::new();
+
+        if self.eat("]") {
+            return Ok(Json::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            if self.eat("]") {
+                return Ok(Json::Array(values));
+            }
+
+            self.expect(",")?;
+        }
+    }
+
+    fn number(&mut self) -> Result<Json> {
+        let len = self.text[self.pos..]
+            .find(|ch: char| !matches!(ch, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+            .unwrap_or(self.text.len() - self.pos);
+
+        let number = &self.text[self.pos..self.pos + len];
+        let value = number
+            .parse()
+            .with_context(|| format!("invalid number '{number}' at byte {}", self.pos))?;
+
+        self.pos += len;
+        Ok(Json::Number(value))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        if !self.text[self.pos..].starts_with('"') {
+            return Err(self.error("a string"));
+        }
+
+        self.pos += 1;
+
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let (_, escape) = chars.next().context("unterminated string")?;
+
+                    out.push(match escape {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => match hex_escape(&mut chars)? {
+                            // characters outside the basic plane are escaped as a pair of surrogates
+                            high @ 0xd800..=0xdbff => {
+                                let low = match (chars.next(), chars.next()) {
+                                    (Some((_, '\\')), Some((_, 'u'))) => hex_escape(&mut chars)?,
+                                    _ => anyhow::bail!("unpaired surrogate '\\u{high:04x}'"),
+                                };
+
+                                if !(0xdc00..=0xdfff).contains(&low) {
+                                    anyhow::bail!("unpaired surrogate '\\u{high:04x}'");
+                                }
+
+                                char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)).unwrap()
+                            }
+                            code => char::from_u32(code)
+                                .with_context(|| format!("unpaired surrogate '\\u{code:04x}'"))?,
+                        },
+                        ch => anyhow::bail!("invalid escape '\\{ch}'"),
+                    });
+                }
+                ch => out.push(ch),
+            }
+        }
+
+        anyhow::bail!("unterminated string")
+    }
+}
+
+// the four hex digits after `\\u`
+fn hex_escape(chars: &mut std::str::CharIndices) -> Result<u32> {
+    let hex: String = chars.by_ref().take(4).map(|(_, ch)| ch).collect();
+
+    if hex.len() != 4 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        anyhow::bail!("invalid escape '\\u{hex}'");
+    }
+
+    Ok(u32::from_str_radix(&hex, 16).unwrap())
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Number(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Json::Null)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(value: Vec<T>) -> Self {
+        Json::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Number(n) if n.is_finite() => write!(f, "{n}"),
+            Json::Number(_) => f.write_str("null"),
+            Json::String(s) => write_escaped(f, s),
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_escaped(f: &mut impl Write, s: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+
+    for ch in s.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            ch if (ch as u32) < 0x20 => write!(f, "\\u{:04x}", ch as u32)?,
+            ch => f.write_char(ch)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_values() {
+        let json = Json::parse(r#" {"a": [1, -2.5e1, true, null], "b": {}, "c": "x\ty"} "#).unwrap();
+
+        assert_eq!(
+            json,
+            Json::object([
+                ("a", Json::Array(vec![1.0.into(), (-25.0).into(), true.into(), Json::Null])),
+                ("b", Json::Object(Vec::new())),
+                ("c", "x\ty".into()),
+            ]),
+        );
+
+        assert_eq!(Json::parse(&json.to_string()).unwrap(), json);
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        assert_eq!(Json::parse(r#""\u00e9\u2603""#).unwrap(), "é☃".into());
+        assert_eq!(Json::parse(r#""\ud83d\ude00!""#).unwrap(), "😀!".into());
+        assert_eq!(Json::parse(r#""\uD83D\uDE00""#).unwrap(), "😀".into());
+    }
+
+    #[test]
+    fn invalid_unicode_escapes() {
+        for text in [
+            r#""\u12""#,
+            r#""\u12"#,
+            r#""\u+123""#,
+            r#""\u12g4""#,
+            r#""\ud83d""#,
+            r#""\ud83dx""#,
+            r#""\ud83d\u0041""#,
+            r#""\ude00""#,
+        ] {
+            assert!(Json::parse(text).is_err(), "{text} should not parse");
+        }
+    }
+
+    #[test]
+    fn trailing_input() {
+        assert!(Json::parse("[1] 2").is_err());
+        assert!(Json::parse("{\"a\": 1,}").is_err());
+    }
+}
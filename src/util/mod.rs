@@ -1,9 +1,18 @@
 pub mod grid;
+pub mod diagnostic;
 pub mod either;
+pub mod json;
+pub mod ocr;
 pub mod reader;
+pub mod rng;
+pub mod terminal;
+pub mod chacha;
 
 pub use self::{
     grid::*,
+    diagnostic::*,
     either::*,
+    json::*,
     reader::*,
+    rng::*,
 };
\ No newline at end of file
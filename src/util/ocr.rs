@@ -0,0 +1,222 @@
+use anyhow::Result;
+
+use super::Grid;
+
+// the letters some puzzles answer with, in the 4x6 font of most years and
+// the 6x10 font of 2018 day 10
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##. #..# #..# #### #..# #..#"),
+    ('B', "###. #..# ###. #..# #..# ###."),
+    ('C', ".##. #..# #... #... #..# .##."),
+    ('E', "#### #... ###. #... #... ####"),
+    ('F', "#### #... ###. #... #... #..."),
+    ('G', ".##. #..# #... #.## #..# .###"),
+    ('H', "#..# #..# #### #..# #..# #..#"),
+    ('I', "### .#. .#. .#. .#. ###"),
+    ('J', "..## ...# ...# ...# #..# .##."),
+    ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+    ('L', "#... #... #... #... #... ####"),
+    ('O', ".##. #..# #..# #..# #..# .##."),
+    ('P', "###. #..# #..# ###. #... #..."),
+    ('R', "###. #..# #..# ###. #.#. #..#"),
+    ('S', ".### #... #... .##. ...# ###."),
+    ('U', "#..# #..# #..# #..# #..# .##."),
+    ('Y', "#...# #...# .#.#. ..#.. ..#.. ..#.."),
+    ('Z', "#### ...# ..#. .#.. #... ####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    ('A', "..##.. .#..#. #....# #....# #....# ###### #....# #....# #....# #....#"),
+    ('B', "#####. #....# #....# #....# #####. #....# #....# #....# #....# #####."),
+    ('C', ".####. #....# #..... #..... #..... #..... #..... #..... #....# .####."),
+    ('E', "###### #..... #..... #..... #####. #..... #..... #..... #..... ######"),
+    ('F', "###### #..... #..... #..... #####. #..... #..... #..... #..... #....."),
+    ('G', ".####. #....# #..... #..... #..... #..### #....# #....# #...## .###.#"),
+    ('H', "#....# #....# #....# #....# ###### #....# #....# #....# #....# #....#"),
+    ('J', "...### ....#. ....#. ....#. ....#. ....#. ....#. #...#. #...#. .###.."),
+    ('K', "#....# #...#. #..#.. #.#... ##.... ##.... #.#... #..#.. #...#. #....#"),
+    ('L', "#..... #..... #..... #..... #..... #..... #..... #..... #..... ######"),
+    ('N', "#....# ##...# ##...# #.#..# #.#..# #..#.# #..#.# #...## #...## #....#"),
+    ('P', "#####. #....# #....# #....# #####. #..... #..... #..... #..... #....."),
+    ('R', "#####. #....# #....# #....# #####. #..#.. #...#. #...#. #....# #....#"),
+    ('X', "#....# #....# .#..#. .#..#. ..##.. ..##.. .#..#. .#..#. #....# #....#"),
+    ('Z', "###### .....# .....# ....#. ...#.. ..#... .#.... #..... #..... ######"),
+];
+
+pub trait Pixel {
+    fn lit(&self) -> bool;
+}
+
+impl Pixel for bool {
+    fn lit(&self) -> bool {
+        *self
+    }
+}
+
+impl Pixel for char {
+    fn lit(&self) -> bool {
+        matches!(self, '#' | '█')
+    }
+}
+
+// a glyph as its columns of pixels, top to bottom
+type Columns = Vec<Vec<bool>>;
+
+fn columns(glyph: &str) -> Columns {
+    let rows: Vec<&str> = glyph.split(' ').collect();
+    let width = rows[0].len();
+
+    (0..width)
+        .map(|x| rows.iter().map(|row| row.as_bytes()[x] == b'#').collect())
+        .collect()
+}
+
+fn render(columns: &[Vec<bool>]) -> String {
+    let height = columns.first().map_or(0, Vec::len);
+
+    (0..height)
+        .map(|y| columns.iter().map(|column| if column[y] { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// reads the letters drawn by the lit pixels, which may sit anywhere in the grid
+pub fn decode<T: Pixel>(grid: &Grid<T>) -> Result<String> {
+    let lit = |x: isize, y: isize| grid.get([x, y]).is_some_and(Pixel::lit);
+
+    let rows: Vec<isize> = (0..grid.height()).filter(|&y| (0..grid.width()).any(|x| lit(x, y))).collect();
+
+    let (Some(&top), Some(&bottom)) = (rows.first(), rows.last())
+    else { anyhow::bail!("grid has no lit pixels to read letters from") };
+
+    let font = match bottom - top + 1 {
+        6 => SMALL,
+        10 => LARGE,
+        height => anyhow::bail!("letters are {height} pixels tall, expected 6 or 10"),
+    };
+
+    let glyphs: Vec<(char, Columns)> = font.iter().map(|&(letter, glyph)| (letter, columns(glyph))).collect();
+
+    let columns: Columns = (0..grid.width())
+        .map(|x| (top..=bottom).map(|y| lit(x, y)).collect())
+        .collect();
+
+    // letters are separated by blank columns, except where a wide one like
+    // `Y` runs into the next, so each run of columns can hold several
+    let mut runs: Vec<&[Vec<bool>]> = columns
+        .split(|column| !column.contains(&true))
+        .filter(|run| !run.is_empty())
+        .collect();
+
+    let mut decoded = String::new();
+    let mut unknown = Vec::new();
+
+    while let Some(run) = runs.first_mut() {
+        let letter = glyphs
+            .iter()
+            .filter(|(_, glyph)| run.starts_with(glyph))
+            .max_by_key(|(_, glyph)| glyph.len());
+
+        match letter {
+            Some((letter, glyph)) => {
+                decoded.push(*letter);
+                *run = &run[glyph.len()..];
+            }
+            None => {
+                decoded.push('?');
+                unknown.push(render(run));
+                *run = &[];
+            }
+        }
+
+        if run.is_empty() {
+            runs.remove(0);
+        }
+    }
+
+    if !unknown.is_empty() {
+        anyhow::bail!(
+            "could not read all letters of '{decoded}', unrecognised glyphs:\n\n{}",
+            unknown.join("\n\n"),
+        );
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::new(&rows.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn small_word() {
+        let letters = grid(&[
+            "#..#.####.#....#.....##.",
+            "#..#.#....#....#....#..#",
+            "####.###..#....#....#..#",
+            "#..#.#....#....#....#..#",
+            "#..#.#....#....#....#..#",
+            "#..#.####.####.####..##.",
+        ]);
+
+        assert_eq!(decode(&letters).unwrap(), "HELLO");
+        assert_eq!(decode(&letters.map(|ch| ch == '#')).unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn large_word_with_margin() {
+        let letters = grid(&[
+            "...............",
+            ".#....#.######.",
+            ".##...#......#.",
+            ".##...#......#.",
+            ".#.#..#.....#..",
+            ".#.#..#....#...",
+            ".#..#.#...#....",
+            ".#..#.#..#.....",
+            ".#...##.#......",
+            ".#...##.#......",
+            ".#....#.######.",
+            "...............",
+        ]);
+
+        assert_eq!(decode(&letters).unwrap(), "NZ");
+    }
+
+    #[test]
+    fn y_touching_next_letter() {
+        let letters = grid(&[
+            "#...#####",
+            "#...#...#",
+            ".#.#...#.",
+            "..#...#..",
+            "..#..#...",
+            "..#..####",
+        ]);
+
+        assert_eq!(decode(&letters).unwrap(), "YZ");
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let letters = grid(&[
+            "#..#.####",
+            "#..#.####",
+            "####.####",
+            "#..#.####",
+            "#..#.####",
+            "#..#.####",
+        ]);
+
+        let error = decode(&letters).unwrap_err().to_string();
+
+        assert_eq!(
+            error,
+            "could not read all letters of 'H?', unrecognised glyphs:\n\n####\n####\n####\n####\n####\n####",
+        );
+    }
+}
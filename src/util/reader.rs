@@ -1,18 +1,35 @@
-use anyhow::{Context, Result};
+use std::cell::Cell;
 
-use super::either::Either;
+use anyhow::Result;
+
+use super::{diagnostic::InputError, either::Either};
 
 #[derive(Clone)]
 pub struct Reader<'a> {
+    // the whole input, `input` is always the part of it still to be read
+    source: &'a str,
     input: &'a str,
+    section: Option<&'static str>,
     skip_whitespace: bool,
+    // where the last error was, lines are counted on from there
+    position: Cell<Position>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Position {
+    offset: usize,
+    line: usize,
+    line_start: usize,
 }
 
 impl<'a> Reader<'a> {
     pub fn new(input: &'a str) -> Self {
         Reader {
+            source: input,
             input,
+            section: None,
             skip_whitespace: true,
+            position: Cell::default(),
         }
     }
 
@@ -21,13 +38,82 @@ impl<'a> Reader<'a> {
         self
     }
 
+    // splits the rest of the input at blank lines into the named sections,
+    // each of which reports errors with its name
+    pub fn sections<const N: usize>(&mut self, names: [&'static str; N]) -> Result<[Reader<'a>; N]> {
+        let mut sections = Vec::new();
+
+        for (i, name) in names.into_iter().enumerate() {
+            // the last section takes the rest, which stays a slice of the
+            // input even when empty so errors can still locate it
+            let (section, rest) = match self.input.split_once("\n\n") {
+                Some(split) if i + 1 < N => split,
+                _ => self.input.split_at(self.input.len()),
+            };
+
+            if section.trim().is_empty() {
+                let expected = match i {
+                    0 => format!("the {name} section"),
+                    _ => format!("the {name} section after a blank line"),
+                };
+
+                return Err(self.error(expected).into());
+            }
+
+            sections.push(Reader {
+                input: section,
+                section: Some(name),
+                ..self.clone()
+            });
+
+            self.input = rest;
+        }
+
+        Ok(sections.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    // an error at the current position, naming what was expected there
+    pub fn error(&self, expected: impl Into<String>) -> InputError {
+        let offset = self.input.as_ptr() as usize - self.source.as_ptr() as usize;
+        let mut position = self.position.get();
+
+        let (from, to) = (position.offset.min(offset), position.offset.max(offset));
+        let newlines = self.source[from..to].bytes().filter(|&b| b == b'\n').count();
+
+        if offset >= position.offset {
+            position.line += newlines;
+        } else {
+            position.line -= newlines;
+        }
+
+        if newlines > 0 {
+            position.line_start = self.source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        }
+
+        position.offset = offset;
+        self.position.set(position);
+
+        let mut error = InputError::new(expected)
+            .line(position.line + 1)
+            .column(offset - position.line_start + 1);
+
+        error.section = self.section;
+
+        // only names what was found where the line or input ends
+        match self.source.as_bytes().get(offset) {
+            None => error.found("end of input"),
+            Some(b'\n' | b'\r') => error.found("end of line"),
+            Some(_) => error,
+        }
+    }
+
     pub fn ident(&mut self) -> Result<&'a str> {
         self.skip_whitespace();
 
         let ident = self.take_while(|ch| ch.is_alphanumeric() || ch == '-' || ch == '_');
 
         if ident.is_empty() {
-            anyhow::bail!("ident not found");
+            return Err(self.error("an identifier").into());
         }
 
         Ok(ident)
@@ -36,50 +122,46 @@ impl<'a> Reader<'a> {
     pub fn digit(&mut self) -> Result<usize> {
         self.skip_whitespace();
 
-        let digit = self.take(1);
-
-        if digit.is_empty() || !digit.chars().next().unwrap().is_ascii_digit() {
-            anyhow::bail!("digit not found");
+        if !self.input.starts_with(|ch: char| ch.is_ascii_digit()) {
+            return Err(self.error("a digit").into());
         }
 
-        Ok(digit.parse().unwrap())
+        Ok(self.take(1).parse().unwrap())
     }
 
     pub fn unsigned(&mut self) -> Result<usize> {
         self.skip_whitespace();
 
+        let start = self.input;
         let unsigned = self.take_while(|ch| ch.is_ascii_digit());
 
         if unsigned.is_empty() {
-            anyhow::bail!("unsigned not found");
+            return Err(self.error("an unsigned integer").into());
         }
 
-        Ok(unsigned.parse().unwrap())
+        unsigned.parse().map_err(|_| {
+            self.input = start;
+            self.error("an unsigned integer that fits in 64 bits").into()
+        })
     }
 
     pub fn signed(&mut self) -> Result<isize> {
         self.skip_whitespace();
 
-        let mut end = 0;
-        if self.input.starts_with('-') {
-            end += 1;
-            self.input = &self.input[1..];
-        }
+        let start = self.input;
+        let sign = usize::from(self.input.starts_with('-'));
+        let digits = self.input[sign..].chars().take_while(char::is_ascii_digit).count();
 
-        for ch in self.input.chars() {
-            if ch.is_ascii_digit() {
-                end += ch.len_utf8();
-            } else {
-                break;
-            }
+        if digits == 0 {
+            return Err(self.error("a signed integer").into());
         }
 
-        let string = (end != 0)
-            .then_some(&self.input[..end])
-            .context("signed not found")?;
-        self.input = &self.input[end..];
+        let string = self.take(sign + digits);
 
-        Ok(string.parse().unwrap())
+        string.parse().map_err(|_| {
+            self.input = start;
+            self.error("a signed integer that fits in 64 bits").into()
+        })
     }
 
     pub fn text(&mut self, text: &str) -> Result<&'a str> {
@@ -90,7 +172,7 @@ impl<'a> Reader<'a> {
             self.input = &self.input[text.len()..];
             Ok(text)
         } else {
-            Err(anyhow::anyhow!("input was not '{text}'"))
+            Err(self.error(format!("'{text}'")).into())
         }
     }
 
@@ -120,7 +202,7 @@ impl<'a> Reader<'a> {
         F: FnMut(&mut Self) -> Result<T>,
     {
         if N == 0 {
-            return Ok([0; N].map(|_| loop {}));
+            return Ok([0; N].map(|_| unreachable!()));
         }
 
         let mut values = Vec::new();
@@ -142,15 +224,10 @@ impl<'a> Reader<'a> {
         }
 
         if count < N {
-            anyhow::bail!("invalid array: expected '{N}' occurences, got '{count}'");
-        }
-
-        let mut array = [0; N];
-        for i in 0..N {
-            array[i] = i;
+            return Err(self.error(format!("{N} values separated by '{separator}', got {count}")).into());
         }
 
-        let array = array.map(|i| values[i].take().unwrap());
+        let array = std::array::from_fn(|i| values[i].take().unwrap());
 
         Ok(array)
     }
@@ -164,6 +241,10 @@ impl<'a> Reader<'a> {
         for line in self.input.lines() {
             self.input = line;
             let item = body(self)?;
+
+            // anything the body left over is a line it couldn't read
+            self.expect_end("the end of the line")?;
+
             out.push(item);
         }
 
@@ -210,27 +291,25 @@ impl<'a> Reader<'a> {
         F1: FnMut(&mut Self) -> Result<T>,
         F2: FnMut(&mut Self) -> Result<U>,
     {
-        let mut last_error;
+        // only the errors at the last position are reported
+        let mut last_errors;
 
         loop {
             let cur = self.input;
 
-            let e1;
-            let e2;
-
-            match body1(self) {
+            let e1 = match body1(self) {
                 Ok(t) => return Ok(Either::Left(t)),
-                Err(err) => e1 = err,
-            }
+                Err(err) => err,
+            };
 
             self.input = cur;
 
-            match body2(self) {
+            let e2 = match body2(self) {
                 Ok(u) => return Ok(Either::Right(u)),
-                Err(err) => e2 = err,
-            }
+                Err(err) => err,
+            };
 
-            last_error = anyhow::anyhow!("errors:\n  left: {e1:?}\n  right: {e2:?}");
+            last_errors = (e1, e2);
 
             let Some(ch) = self.input.chars().next()
             else { break };
@@ -238,7 +317,8 @@ impl<'a> Reader<'a> {
             self.input = &cur[ch.len_utf8()..];
         }
 
-        Err(last_error)
+        let (e1, e2) = last_errors;
+        Err(anyhow::anyhow!("errors:\n  left: {e1:?}\n  right: {e2:?}"))
     }
 
     pub fn take(&mut self, n: usize) -> &'a str {
@@ -252,6 +332,21 @@ impl<'a> Reader<'a> {
         self.input.is_empty()
     }
 
+    // fails unless only whitespace is left, for parsers like `while_ok` that
+    // stop quietly at the first thing they can't read
+    pub fn end(&mut self) -> Result<()> {
+        self.expect_end("the end of the input")
+    }
+
+    fn expect_end(&mut self, expected: &str) -> Result<()> {
+        self.input = self.input.trim_start();
+
+        match self.input.lines().next() {
+            None => Ok(()),
+            Some(rest) => Err(self.error(expected).found(format!("'{}'", rest.trim_end())).into()),
+        }
+    }
+
     fn take_while<F>(&mut self, mut cond: F) -> &'a str
     where
         F: FnMut(char) -> bool,
@@ -300,4 +395,94 @@ where
     fn reader(self) -> Reader<'a> {
         Reader::new(self.as_ref())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(e: &anyhow::Error) -> (Option<&'static str>, Option<usize>, Option<usize>) {
+        let e = e.downcast_ref::<InputError>().expect("an InputError");
+        (e.section, e.line, e.column)
+    }
+
+    fn pair(r: &mut Reader) -> Result<(usize, usize)> {
+        Ok((r.unsigned()?, r.unsigned()?))
+    }
+
+    #[test]
+    fn error_lines_and_columns() {
+        let e = "1 2\n3 x\n4 5\n".reader().lines(pair).unwrap_err();
+        assert_eq!(location(&e), (None, Some(2), Some(3)));
+
+        // the last line without a trailing newline
+        let e = "1 2\n3 4\n5 y".reader().lines(pair).unwrap_err();
+        assert_eq!(location(&e), (None, Some(3), Some(3)));
+
+        let e = "1 2\n3".reader().lines(pair).unwrap_err();
+        assert_eq!(location(&e), (None, Some(2), Some(2)));
+        assert_eq!(e.downcast_ref::<InputError>().unwrap().found.as_deref(), Some("end of input"));
+
+        let e = "1 2\n3\n".reader().lines(pair).unwrap_err();
+        assert_eq!(location(&e), (None, Some(2), Some(2)));
+        assert_eq!(e.downcast_ref::<InputError>().unwrap().found.as_deref(), Some("end of line"));
+    }
+
+    #[test]
+    fn error_locations_after_backtracking() {
+        let source = "ab\n\ncde\nf\n\ngh";
+        let mut r = Reader::new(source);
+
+        let forward: Vec<usize> = (0..=source.len()).collect();
+        let backward: Vec<usize> = forward.iter().rev().copied().collect();
+        let jumps = [7, 1, 12, 3, 0, 9, 4, 13, 2, 6];
+
+        for offset in forward.into_iter().chain(backward).chain(jumps) {
+            r.input = &source[offset..];
+
+            let line = source[..offset].matches('\n').count() + 1;
+            let column = offset - source[..offset].rfind('\n').map_or(0, |i| i + 1) + 1;
+
+            let e = r.error("anything");
+            assert_eq!((e.line, e.column), (Some(line), Some(column)), "offset {offset}");
+        }
+    }
+
+    #[test]
+    fn error_sections() {
+        let mut r = "1 2\n\n3 4\n5 z\n".reader();
+        let [_, mut updates] = r.sections(["rules", "updates"]).unwrap();
+
+        let e = updates.lines(pair).unwrap_err();
+        assert_eq!(location(&e), (Some("updates"), Some(4), Some(3)));
+
+        let Err(e) = "1 2\n\n".reader().sections(["rules", "updates"])
+        else { panic!("an empty section should fail") };
+        assert_eq!(location(&e), (None, Some(3), Some(1)));
+    }
+
+    #[test]
+    fn lines_reject_leftover_input() {
+        let e = "1 2\n3 4 5\n6 7\n".reader().lines(pair).unwrap_err();
+        let e = e.downcast_ref::<InputError>().unwrap();
+
+        assert_eq!((e.line, e.column), (Some(2), Some(5)));
+        assert_eq!(e.expected, "the end of the line");
+        assert_eq!(e.found.as_deref(), Some("'5'"));
+
+        assert_eq!("1 2  \n3 4\n".reader().lines(pair).unwrap(), [(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn end_rejects_leftover_input() {
+        let mut r = "125 17x".reader();
+        assert_eq!(r.while_ok(|r| r.unsigned()), [125, 17]);
+
+        let e = r.end().unwrap_err();
+        assert_eq!(location(&e), (None, Some(1), Some(7)));
+
+        let mut r = "125 17 \n".reader();
+        r.while_ok(|r| r.unsigned());
+        assert!(r.end().is_ok());
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+// splitmix64, good enough for generating puzzle inputs
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+
+        Rng::new(nanos as u64)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        assert!(!range.is_empty(), "empty range");
+
+        let len = (range.end - range.start) as u64;
+        range.start + (self.next_u64() % len) as usize
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    // a `width` by `height` grid with newline terminated rows
+    pub fn grid(&mut self, width: usize, height: usize, mut cell: impl FnMut(&mut Rng) -> char) -> String {
+        let mut grid = String::with_capacity((width + 1) * height);
+
+        for _ in 0..height {
+            for _ in 0..width {
+                grid.push(cell(self));
+            }
+            grid.push('\n');
+        }
+
+        grid
+    }
+}
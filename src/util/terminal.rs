@@ -0,0 +1,76 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+
+// styles shared by everything drawing to the terminal
+pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const UNDERLINE: &str = "\x1b[4m";
+pub const REVERSE: &str = "\x1b[7m";
+pub const RED: &str = "\x1b[31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const YELLOW: &str = "\x1b[33m";
+pub const BLUE: &str = "\x1b[34m";
+pub const MAGENTA: &str = "\x1b[35m";
+pub const CYAN: &str = "\x1b[36m";
+pub const RESET: &str = "\x1b[0m";
+
+// rows of the terminal on stdout, or a usual default when it isn't one
+pub fn height() -> usize {
+    let mut size = libc::winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+
+    // SAFETY: TIOCGWINSZ only writes the size into the struct it is given
+    let res = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+
+    match res == 0 && size.ws_row > 0 {
+        true => size.ws_row as usize,
+        false => 24,
+    }
+}
+
+// raw input on the alternate screen, both undone when dropped
+pub struct RawMode {
+    original: libc::termios,
+}
+
+impl RawMode {
+    pub fn enable() -> Result<Self> {
+        // SAFETY: termios is plain data that tcgetattr fills in
+        let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+
+        // SAFETY: both calls only access the struct they are given
+        unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return Err(std::io::Error::last_os_error()).context("could not read terminal settings");
+            }
+
+            let original = termios;
+            libc::cfmakeraw(&mut termios);
+
+            // keep output processing so the progress bars and logs still start
+            // their lines at the left edge
+            termios.c_oflag |= libc::OPOST;
+
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) != 0 {
+                return Err(std::io::Error::last_os_error()).context("could not enable raw mode");
+            }
+
+            print!("\x1b[?1049h\x1b[?25l");
+            std::io::stdout().flush()?;
+
+            Ok(RawMode { original })
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        std::io::stdout().flush().ok();
+
+        // SAFETY: restores the settings read when raw mode was enabled
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
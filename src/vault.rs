@@ -0,0 +1,189 @@
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::util::chacha::{self, KEY_LEN, NONCE_LEN};
+
+// puzzle inputs and descriptions aren't meant to be shared, so once a key
+// exists they are kept encrypted with it and can be committed safely
+const MAGIC: &[u8] = b"aoc-sealed-v1\n";
+
+pub fn key_path() -> PathBuf {
+    std::env::var_os("AOC_KEY_FILE").map_or_else(|| PathBuf::from("input.key"), PathBuf::from)
+}
+
+pub fn load_key() -> Result<Option<[u8; KEY_LEN]>> {
+    let path = key_path();
+
+    let hex = match std::fs::read_to_string(&path) {
+        Ok(hex) => hex,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("could not read key '{}'", path.display())),
+    };
+
+    let hex = hex.trim();
+
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        anyhow::bail!("key '{}' is not {KEY_LEN} hex encoded bytes", path.display());
+    }
+
+    let mut key = [0; KEY_LEN];
+
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .with_context(|| format!("key '{}' is not hex encoded", path.display()))?;
+    }
+
+    Ok(Some(key))
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut bytes))
+        .context("could not read random bytes")?;
+
+    Ok(bytes)
+}
+
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let nonce: [u8; NONCE_LEN] = random_bytes()?;
+
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&nonce);
+    out.extend(chacha::seal(key, &nonce, MAGIC, plaintext));
+    Ok(out)
+}
+
+fn open(key: &[u8; KEY_LEN], sealed: &[u8]) -> Result<Vec<u8>> {
+    let rest = &sealed[MAGIC.len()..];
+
+    if rest.len() < NONCE_LEN {
+        anyhow::bail!("sealed file is truncated");
+    }
+
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    chacha::open(key, nonce.try_into().unwrap(), MAGIC, ciphertext)
+}
+
+// like `fs::read_to_string`, decrypting sealed files
+pub fn read(path: &Path) -> io::Result<String> {
+    let bytes = std::fs::read(path)?;
+
+    let bytes = match is_sealed(&bytes) {
+        false => bytes,
+        true => {
+            let key = load_key()
+                .map_err(io::Error::other)?
+                .ok_or_else(|| io::Error::other(format!("'{}' is encrypted but there is no key", path.display())))?;
+
+            open(&key, &bytes).map_err(io::Error::other)?
+        }
+    };
+
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// like `fs::write`, encrypting whenever a key exists
+pub fn write(path: &Path, text: &str) -> io::Result<()> {
+    let contents = match load_key().map_err(io::Error::other)? {
+        None => text.as_bytes().to_vec(),
+        Some(key) => seal(&key, text.as_bytes()).map_err(io::Error::other)?,
+    };
+
+    std::fs::write(path, contents)
+}
+
+// inputs and puzzle descriptions, but not answers, times or cached responses
+fn protected_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_context(|| format!("could not list '{}'", dir.display())),
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            protected_files(&path, files)?;
+        } else if !name.ends_with(".answers") && !name.ends_with(".times") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// encrypts every input, creating the key on first use
+pub fn lock() -> Result<usize> {
+    let key = match load_key()? {
+        Some(key) => key,
+        None => {
+            let key: [u8; KEY_LEN] = random_bytes()?;
+            let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+
+            let path = key_path();
+            std::fs::write(&path, hex + "\n")
+                .with_context(|| format!("could not write key '{}'", path.display()))?;
+            println!("created key '{}', keep it out of version control", path.display());
+
+            key
+        }
+    };
+
+    let mut files = Vec::new();
+    protected_files(Path::new("input"), &mut files)?;
+
+    let mut count = 0;
+
+    for path in files {
+        let bytes = std::fs::read(&path).with_context(|| format!("could not read '{}'", path.display()))?;
+
+        if !is_sealed(&bytes) {
+            std::fs::write(&path, seal(&key, &bytes)?)
+                .with_context(|| format!("could not write '{}'", path.display()))?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+// decrypts every input and removes the key, so new inputs are stored as is
+pub fn unlock() -> Result<usize> {
+    let key = load_key()?.context("there is no key, inputs are not encrypted")?;
+
+    let mut files = Vec::new();
+    protected_files(Path::new("input"), &mut files)?;
+
+    let mut count = 0;
+
+    for path in files {
+        let bytes = std::fs::read(&path).with_context(|| format!("could not read '{}'", path.display()))?;
+
+        if is_sealed(&bytes) {
+            let plaintext = open(&key, &bytes).with_context(|| format!("could not decrypt '{}'", path.display()))?;
+            std::fs::write(&path, plaintext).with_context(|| format!("could not write '{}'", path.display()))?;
+            count += 1;
+        }
+    }
+
+    let path = key_path();
+    std::fs::remove_file(&path).with_context(|| format!("could not remove key '{}'", path.display()))?;
+
+    Ok(count)
+}
@@ -0,0 +1,160 @@
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+
+use crate::input::{Source, input_path};
+
+const POLL: Duration = Duration::from_millis(500);
+
+pub struct Watch {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub source: Source,
+}
+
+impl Watch {
+    // the day's module, the shared utilities and every input of the day
+    fn watched(&self) -> Vec<PathBuf> {
+        let mut paths = vec![PathBuf::from(format!("src/y{}/day{}.rs", self.year, self.day))];
+
+        paths.extend(files_in(Path::new("src/util")));
+
+        if let Some(dir) = input_path(self.year, self.day, &self.source).parent() {
+            let prefix = self.day.to_string();
+
+            paths.extend(files_in(dir).into_iter().filter(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                name.strip_prefix(&prefix)
+                    .is_some_and(|rest| !rest.starts_with(|ch: char| ch.is_ascii_digit()))
+            }));
+        }
+
+        paths
+    }
+
+    // inputs like `12` and `12example`, examples first since they're quick
+    fn suffixes(&self) -> Vec<String> {
+        let prefix = self.day.to_string();
+
+        let mut suffixes: Vec<String> = self
+            .watched()
+            .iter()
+            .filter(|path| path.starts_with("input"))
+            .filter_map(|path| path.file_name()?.to_str()?.strip_prefix(&prefix).map(str::to_string))
+            .filter(|suffix| !suffix.contains('.'))
+            .collect();
+
+        // the real input is downloaded by the first run if it's missing
+        if !suffixes.contains(&String::new()) {
+            suffixes.push(String::new());
+        }
+
+        suffixes.sort_by_key(|suffix| (suffix.is_empty(), suffix.clone()));
+        suffixes
+    }
+
+    fn snapshot(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        self.watched()
+            .into_iter()
+            .map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+
+    pub fn run(&self) -> Result<()> {
+        // looked up before cargo replaces it, after that it points at a deleted file
+        let exe = std::env::current_exe().context("could not find the runner binary")?;
+
+        let mut last = self.snapshot();
+        self.rebuild_and_run(&exe)?;
+
+        loop {
+            std::thread::sleep(POLL);
+
+            let current = self.snapshot();
+            if current == last {
+                continue;
+            }
+
+            // editors often write files in several steps
+            std::thread::sleep(POLL / 5);
+            last = self.snapshot();
+
+            self.rebuild_and_run(&exe)?;
+        }
+    }
+
+    fn rebuild_and_run(&self, exe: &Path) -> Result<()> {
+        if std::io::stdout().is_terminal() {
+            print!("\x1b[2J\x1b[H");
+        }
+
+        println!("y{} day {} part {}, watching for changes", self.year, self.day, self.part);
+
+        // the same profile and features as this binary, which cargo then replaces
+        let mut features: Vec<String> = crate::COMPILED_YEARS.iter().map(|year| format!("y{year}")).collect();
+
+        if cfg!(feature = "alloc-stats") {
+            features.push("alloc-stats".to_string());
+        }
+
+        let mut build = Command::new("cargo");
+        build.args(["build", "--quiet", "--no-default-features"]);
+        build.arg("--features").arg(features.join(","));
+
+        if !cfg!(debug_assertions) {
+            build.arg("--release");
+        }
+
+        if !build.status().context("could not run cargo")?.success() {
+            println!("build failed");
+            return Ok(());
+        }
+
+        for suffix in self.suffixes() {
+            match suffix.is_empty() {
+                true => println!("\n--- input"),
+                false => println!("\n--- input {suffix}"),
+            }
+
+            let mut run = Command::new(exe);
+
+            if let Some(user) = &self.source.user {
+                run.args(["--user", user]);
+            }
+
+            run.arg(format!("y{}", self.year))
+                .arg(self.day.to_string())
+                .arg(self.part.to_string());
+
+            if !suffix.is_empty() {
+                run.arg(&suffix);
+            }
+
+            run.status().context("could not run the part")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir)
+    else { return Vec::new() };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+
+    files.sort();
+    files
+}
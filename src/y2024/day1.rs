@@ -1,8 +1,8 @@
 use anyhow::Result;
 
-use crate::util::IntoReader;
+use crate::{runner::Answer, util::IntoReader};
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let mut r = input.reader();
     
     let lines = r.lines(|r| Ok((r.unsigned()?, r.unsigned()?)))?;
@@ -16,12 +16,10 @@ pub fn part1(input: String) -> Result<()> {
         .map(|(a, b)| a.abs_diff(b))
         .sum();
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let mut r = input.reader();
     
     let lines = r.lines(|r| Ok((r.unsigned()?, r.unsigned()?)))?;
@@ -35,7 +33,5 @@ pub fn part2(input: String) -> Result<()> {
         score += num * occurences;
     }
 
-    println!("{score}");
-
-    Ok(())
+    Ok(score.into())
 }
\ No newline at end of file
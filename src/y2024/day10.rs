@@ -1,8 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
-use crate::util::{CellIndex, Grid, IntoGrid};
+use crate::{runner::Answer, util::{CellIndex, Grid, IntoGrid, Rng}};
 
 fn generate_path(grid: &Grid, start: CellIndex) -> (HashSet<CellIndex>, usize) {
     let mut path = HashSet::new();
@@ -33,7 +33,7 @@ fn generate_path(grid: &Grid, start: CellIndex) -> (HashSet<CellIndex>, usize) {
     (path, nine_reached)
 }
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let grid = input.grid()?;
 
     let zeros = grid
@@ -54,12 +54,10 @@ pub fn part1(input: String) -> Result<()> {
         sum += nines;
     }
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let grid = input.grid()?;
 
     let zeros = grid
@@ -75,7 +73,105 @@ pub fn part2(input: String) -> Result<()> {
         sum += reached;
     }
 
-    println!("{sum}");
+    Ok(sum.into())
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let width = rng.range(1..9);
+    let height = rng.range(1..9);
+
+    let mut grid = rng
+        .grid(width, height, |rng| char::from_digit(rng.range(0..10) as u32, 10).unwrap())
+        .grid()
+        .unwrap();
+
+    // random digits rarely form trails, so carve a few in
+    for _ in 0..rng.range(0..4) {
+        let mut cur = CellIndex::from([rng.range(0..width), rng.range(0..height)]);
+
+        for height in '0'..='9' {
+            grid.set(cur, height).unwrap();
+
+            let next: Vec<CellIndex> = grid.cardinal(cur).map(|(i, _)| i).collect();
+            if next.is_empty() {
+                break;
+            }
+            cur = *rng.pick(&next);
+        }
+    }
+
+    grid.to_string()
+}
+
+// the nines reachable from each cell, built from the top of the trails down
+fn reachable_nines(grid: &Grid) -> HashMap<CellIndex, HashSet<CellIndex>> {
+    let mut reachable: HashMap<CellIndex, HashSet<CellIndex>> = HashMap::new();
+
+    for height in ('0'..='9').rev() {
+        for (i, &ch) in grid {
+            if ch != height {
+                continue;
+            }
+
+            let nines = match height {
+                '9' => HashSet::from([i]),
+                _ => grid
+                    .cardinal(i)
+                    .filter(|(_, adj)| **adj as u8 == height as u8 + 1)
+                    .flat_map(|(adj, _)| reachable[&adj].clone())
+                    .collect(),
+            };
+
+            reachable.insert(i, nines);
+        }
+    }
+
+    reachable
+}
+
+pub fn part1_oracle(input: String) -> Result<Answer> {
+    let grid = input.grid()?;
+    let reachable = reachable_nines(&grid);
+
+    let sum: usize = grid
+        .iter()
+        .filter(|(_, ch)| **ch == '0')
+        .map(|(i, _)| reachable[&i].len())
+        .sum();
+
+    Ok(sum.into())
+}
+
+// counts distinct trails with the same top-down pass
+pub fn part2_oracle(input: String) -> Result<Answer> {
+    let grid = input.grid()?;
+
+    let mut trails: HashMap<CellIndex, usize> = HashMap::new();
+
+    for height in ('0'..='9').rev() {
+        for (i, &ch) in &grid {
+            if ch != height {
+                continue;
+            }
+
+            let count = match height {
+                '9' => 1,
+                _ => grid
+                    .cardinal(i)
+                    .filter(|(_, adj)| **adj as u8 == height as u8 + 1)
+                    .map(|(adj, _)| trails[&adj])
+                    .sum(),
+            };
+
+            trails.insert(i, count);
+        }
+    }
+
+    let sum: usize = grid
+        .iter()
+        .filter(|(_, ch)| **ch == '0')
+        .map(|(i, _)| trails[&i])
+        .sum();
 
-    Ok(())
+    Ok(sum.into())
 }
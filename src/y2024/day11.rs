@@ -1,7 +1,7 @@
 use anyhow::Result;
 use cached::proc_macro::cached;
 
-use crate::util::IntoReader;
+use crate::{runner::{Answer, Memo, param}, util::IntoReader};
 
 #[cached]
 fn blink(stone: u64, times: usize) -> usize {
@@ -16,7 +16,7 @@ fn blink(stone: u64, times: usize) -> usize {
 
     let num_digits = stone.checked_ilog10().unwrap_or(0) + 1;
 
-    if num_digits % 2 == 0 {
+    if num_digits.is_multiple_of(2) {
         let stone_str = stone.to_string();
         let (l, r) = stone_str.split_at(stone_str.len() / 2);
         let l: u64 = l.parse().unwrap();
@@ -30,6 +30,10 @@ fn blink(stone: u64, times: usize) -> usize {
     blink(stone * 2024, times - 1)
 }
 
+pub fn blink_cache() -> &'static dyn Memo {
+    &*BLINK
+}
+
 fn run(stones: &[u64], times: usize) -> usize {
     stones
         .iter()
@@ -37,22 +41,31 @@ fn run(stones: &[u64], times: usize) -> usize {
         .sum()
 }
 
-pub fn part1(input: String) -> Result<()> {
-    let stones = input.reader().while_ok(|r| r.unsigned().map(|u| u as u64));
+fn parse(input: &str) -> Result<Vec<u64>> {
+    let mut r = input.reader();
+    let stones = r.while_ok(|r| r.unsigned().map(|u| u as u64));
 
-    let sum = run(&stones, 25);
+    if stones.is_empty() {
+        return Err(r.error("a stone's number").into());
+    }
 
-    println!("{}", sum);
+    r.end()?;
 
-    Ok(())
+    Ok(stones)
 }
 
-pub fn part2(input: String) -> Result<()> {
-    let stones = input.reader().while_ok(|r| r.unsigned().map(|u| u as u64));
+pub fn part1(input: String) -> Result<Answer> {
+    let stones = parse(&input)?;
+
+    let sum = run(&stones, param("blinks", 25)?);
+
+    Ok(sum.into())
+}
 
-    let sum = run(&stones, 75);
+pub fn part2(input: String) -> Result<Answer> {
+    let stones = parse(&input)?;
 
-    println!("{}", sum);
+    let sum = run(&stones, param("blinks", 75)?);
 
-    Ok(())
+    Ok(sum.into())
 }
@@ -1,8 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
-use crate::util::{CellIndex, Grid, IntoGrid};
+use crate::{runner::{Answer, Color, Frame, Frames}, util::{CellIndex, Grid, IntoGrid, Rng}};
 
 fn fill(grid: &Grid, from: CellIndex, filter: char) -> HashSet<CellIndex> {
     let mut found = HashSet::new();
@@ -124,7 +124,7 @@ fn sides(plot: &HashSet<CellIndex>, grid: &Grid) -> usize {
 
 fn area(plot: &HashSet<CellIndex>) -> usize { plot.len() }
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let grid = input.grid()?;
 
     let mut plots = Vec::<HashSet<CellIndex>>::new();
@@ -144,12 +144,10 @@ pub fn part1(input: String) -> Result<()> {
         .map(|plot| area(plot) * perimeter(plot))
         .sum();
 
-    println!("{total}");
-
-    Ok(())
+    Ok(total.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let grid = input.grid()?;
 
     let mut plots = Vec::<HashSet<CellIndex>>::new();
@@ -169,7 +167,126 @@ pub fn part2(input: String) -> Result<()> {
         .map(|plot| area(plot) * sides(plot, &grid))
         .sum();
 
-    println!("{total}");
+    Ok(total.into())
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let width = rng.range(1..9);
+    let height = rng.range(1..9);
+
+    rng.grid(width, height, |rng| *rng.pick(&['A', 'B', 'C']))
+}
+
+// every region once, found with a flood fill that never revisits a cell
+fn regions(grid: &Grid) -> Vec<HashSet<CellIndex>> {
+    let mut seen = HashSet::new();
+    let mut regions = Vec::new();
+
+    for (start, &plant) in grid {
+        if !seen.insert(start) {
+            continue;
+        }
+
+        let mut region = HashSet::from([start]);
+        let mut queue = vec![start];
+
+        while let Some(cur) = queue.pop() {
+            for (adj, &adj_plant) in grid.cardinal(cur) {
+                if adj_plant == plant && seen.insert(adj) {
+                    region.insert(adj);
+                    queue.push(adj);
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    regions
+}
+
+pub fn part1_oracle(input: String) -> Result<Answer> {
+    let grid = input.grid()?;
+
+    let total: usize = regions(&grid)
+        .iter()
+        .map(|region| region.len() * perimeter(region))
+        .sum();
+
+    Ok(total.into())
+}
+
+// a polygon has as many sides as corners
+fn corners(region: &HashSet<CellIndex>) -> usize {
+    let offsets = [(0isize, -1isize), (1, 0), (0, 1), (-1, 0)].map(CellIndex::from);
+
+    region
+        .iter()
+        .map(|&cell| {
+            (0..4)
+                .filter(|&i| {
+                    let a = region.contains(&(cell + offsets[i]));
+                    let b = region.contains(&(cell + offsets[(i + 1) % 4]));
+                    let diagonal = region.contains(&(cell + offsets[i] + offsets[(i + 1) % 4]));
+
+                    (!a && !b) || (a && b && !diagonal)
+                })
+                .count()
+        })
+        .sum()
+}
+
+pub fn part2_oracle(input: String) -> Result<Answer> {
+    let grid = input.grid()?;
+
+    let total: usize = regions(&grid)
+        .iter()
+        .map(|region| region.len() * corners(region))
+        .sum();
+
+    Ok(total.into())
+}
+
+// every region as it's found, each in a colour none of its neighbours have
+pub fn visualize(input: String, frames: &mut Frames) -> Result<()> {
+    let grid = input.grid()?;
+
+    let mut colors: HashMap<CellIndex, Color> = HashMap::new();
+    let mut price = 0;
+
+    for (i, region) in regions(&grid).iter().enumerate() {
+        let taken: HashSet<Color> = region
+            .iter()
+            .flat_map(|cell| cell.cardinal())
+            .filter_map(|adj| colors.get(&adj).copied())
+            .collect();
+
+        let color = Color::ALL
+            .into_iter()
+            .find(|color| !taken.contains(color))
+            .unwrap_or(Color::ALL[i % Color::ALL.len()]);
+
+        colors.extend(region.iter().map(|&cell| (cell, color)));
+        price += region.len() * perimeter(region);
+
+        let plant = grid.get(*region.iter().next().unwrap()).unwrap();
+
+        let frame = Color::ALL
+            .into_iter()
+            .fold(Frame::from(&grid), |frame, color| {
+                let cells = colors.iter().filter(|(_, c)| **c == color).map(|(cell, _)| *cell);
+                frame.paint(cells, color)
+            })
+            .caption(format!(
+                "region {} of {plant}: area {}, perimeter {}, sides {}, total price {price}",
+                i + 1,
+                region.len(),
+                perimeter(region),
+                corners(region),
+            ));
+
+        frames.push(frame)?;
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
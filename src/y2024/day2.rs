@@ -1,31 +1,37 @@
 use anyhow::Result;
 
-use crate::util::IntoReader;
+use crate::{runner::Answer, util::{IntoReader, Reader}};
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let num_safe = input
         .reader()
-        .lines(|r| Ok(r.while_ok(|r| r.unsigned())))?
+        .lines(report)?
         .into_iter()
         .filter(|nums| is_safe(nums, nums.len()))
         .count();
 
-    println!("{num_safe}");
-
-    Ok(())
+    Ok(num_safe.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let num_safe = input
         .reader()
-        .lines(|r| Ok(r.while_ok(|r| r.unsigned())))?
+        .lines(report)?
         .into_iter()
         .filter(|nums| is_safe(nums, nums.len()) || (0..nums.len()).any(|skip| is_safe(nums, skip)))
         .count();
 
-    println!("{num_safe}");
+    Ok(num_safe.into())
+}
+
+fn report(r: &mut Reader) -> Result<Vec<usize>> {
+    let levels = r.while_ok(|r| r.unsigned());
+
+    if levels.is_empty() {
+        return Err(r.error("a report of levels").into());
+    }
 
-    Ok(())
+    Ok(levels)
 }
 
 fn is_safe(nums: &[usize], skip: usize) -> bool {
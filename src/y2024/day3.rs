@@ -1,6 +1,6 @@
 use anyhow::Result;
 
-use crate::util::{Either, IntoReader, Reader};
+use crate::{runner::Answer, util::{Either, IntoReader, Reader}};
 
 fn mul(r: &mut Reader) -> Result<(usize, usize)> {
     r.text("mul(")?;
@@ -11,19 +11,17 @@ fn mul(r: &mut Reader) -> Result<(usize, usize)> {
     Ok((a, b))
 }
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let mut sum = 0;
 
     for (a, b) in input.reader().keep_whitespace().get_matches(mul) {
         sum += a * b;
     }
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let mut enabled = true;
     let mut sum = 0;
 
@@ -48,7 +46,5 @@ pub fn part2(input: String) -> Result<()> {
         }
     }
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
\ No newline at end of file
@@ -1,19 +1,57 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
 
-use crate::util::{GridMask, IntoGrid};
+use crate::{runner::{Answer, param}, util::{Grid, GridMask, IntoGrid, Rng}};
 
-pub fn part1(input: String) -> Result<()> {
-    let masks = [
-        "XMAS",
-        "SAMX",
-        "X\nM\nA\nS",
-        "S\nA\nM\nX",
-        "X...\n.M..\n..A.\n...S",
-        "S...\n.A..\n..M.\n...X",
-        "...X\n..M.\n.A..\nS...",
-        "...S\n..A.\n.M..\nX...",
-    ];
-    let masks = masks.map(|mask| GridMask::new_mask(mask, '.').unwrap());
+fn word() -> Result<String> {
+    let word = param::<String>("word", "XMAS".to_string())?;
+
+    if word.is_empty() {
+        anyhow::bail!("parameter 'word' must not be empty");
+    }
+
+    Ok(word)
+}
+
+// the word forwards and backwards in every direction
+fn word_masks(word: &str) -> Result<Vec<GridMask<char>>> {
+    let mut masks = Vec::new();
+
+    for word in [word.to_string(), word.chars().rev().collect()] {
+        let chars: Vec<char> = word.chars().collect();
+        let n = chars.len();
+
+        let diagonal = |flip: bool| {
+            let rows: Vec<String> = chars
+                .iter()
+                .enumerate()
+                .map(|(i, ch)| {
+                    let before = if flip { n - 1 - i } else { i };
+                    format!("{}{ch}{}", ".".repeat(before), ".".repeat(n - 1 - before))
+                })
+                .collect();
+            rows.join("\n")
+        };
+
+        masks.push(word.clone());
+        masks.push(chars.iter().map(char::to_string).collect::<Vec<_>>().join("\n"));
+        masks.push(diagonal(false));
+        masks.push(diagonal(true));
+    }
+
+    // palindromes would otherwise be counted twice
+    masks.sort();
+    masks.dedup();
+
+    masks
+        .iter()
+        .map(|mask| GridMask::new_mask(mask, '.'))
+        .collect()
+}
+
+pub fn part1(input: String) -> Result<Answer> {
+    let masks = word_masks(&word()?)?;
 
     let grid = input.grid()?;
 
@@ -29,12 +67,10 @@ pub fn part1(input: String) -> Result<()> {
         }
     }
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let masks = [
         "M.S\n.A.\nM.S",
         "M.M\n.A.\nS.S",
@@ -60,7 +96,69 @@ pub fn part2(input: String) -> Result<()> {
         }
     }
 
-    println!("{sum}");
+    Ok(sum.into())
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let width = rng.range(1..9);
+    let height = rng.range(1..9);
+
+    rng.grid(width, height, |rng| *rng.pick(&['X', 'M', 'A', 'S']))
+}
+
+fn word_at(grid: &Grid, word: &[char], [x, y]: [isize; 2], [dx, dy]: [isize; 2]) -> bool {
+    word.iter()
+        .enumerate()
+        .all(|(i, ch)| grid.get([x + dx * i as isize, y + dy * i as isize]) == Some(ch))
+}
+
+// walks every direction from every cell instead of matching masks
+pub fn part1_oracle(input: String) -> Result<Answer> {
+    let word: Vec<char> = word()?.chars().collect();
+
+    let grid = input.grid()?;
+
+    // palindromes read the same from either end and single letters the same in
+    // every direction, so each match is kept once by the cells it covers
+    let mut found = HashSet::new();
+
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            for [dx, dy] in [[1, 0], [-1, 0], [0, 1], [0, -1], [1, 1], [-1, -1], [1, -1], [-1, 1]] {
+                if word_at(&grid, &word, [x, y], [dx, dy]) {
+                    let mut cells: Vec<[isize; 2]> = (0..word.len() as isize)
+                        .map(|i| [x + dx * i, y + dy * i])
+                        .collect();
+                    cells.sort();
+
+                    found.insert(cells);
+                }
+            }
+        }
+    }
+
+    Ok(found.len().into())
+}
+
+pub fn part2_oracle(input: String) -> Result<Answer> {
+    let grid = input.grid()?;
+
+    let is_mas = |a: Option<&char>, b: Option<&char>| {
+        matches!((a, b), (Some('M'), Some('S')) | (Some('S'), Some('M')))
+    };
+
+    let mut sum = 0;
+
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            if grid.get([x, y]) == Some(&'A')
+                && is_mas(grid.get([x - 1, y - 1]), grid.get([x + 1, y + 1]))
+                && is_mas(grid.get([x + 1, y - 1]), grid.get([x - 1, y + 1]))
+            {
+                sum += 1;
+            }
+        }
+    }
 
-    Ok(())
+    Ok(sum.into())
 }
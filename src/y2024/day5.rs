@@ -2,43 +2,55 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
-use crate::util::IntoReader;
+use crate::{runner::{Answer, phase}, util::IntoReader};
 
 fn obeys_rule(rule_map: &HashMap<usize, HashSet<usize>>, update: &[usize], i_a: usize) -> Result<(), usize> {
     let a = update[i_a];
     let Some(bs) = rule_map.get(&a)
     else { return Ok(()) };
 
-    for (i_b, b) in update.into_iter().enumerate() {
-        if bs.contains(b) {
-            if i_b < i_a {
-                return Err(i_b);
-            }
+    for (i_b, b) in update.iter().enumerate() {
+        if bs.contains(b) && i_b < i_a {
+            return Err(i_b);
         }
     }
 
     Ok(())
 }
 
-pub fn part1(input: String) -> Result<()> {
-    let (rules, pages) = input.split_once("\n\n").unwrap();
+pub fn part1(input: String) -> Result<Answer> {
+    let (rules, updates) = {
+        let _s = phase("parse");
 
-    let rules = rules.reader().lines(|r| {
-        let a = r.unsigned()?;
-        r.text("|")?;
-        let b = r.unsigned()?;
-        Ok((a, b))
-    })?;
+        let [mut rules, mut updates] = input.reader().sections(["rules", "updates"])?;
 
-    let mut rule_map = HashMap::new();
+        let rules = rules.lines(|r| {
+            let a = r.unsigned()?;
+            r.text("|")?;
+            let b = r.unsigned()?;
+            Ok((a, b))
+        })?;
 
-    for (a, b) in rules {
-        rule_map.entry(a).or_insert(HashSet::new()).insert(b);
-    }
+        let updates = updates.lines(|r| {
+            r.list(",", |r| r.unsigned())
+        })?;
+
+        (rules, updates)
+    };
+
+    let rule_map = {
+        let _s = phase("rule map");
+
+        let mut rule_map = HashMap::new();
 
-    let updates = pages.reader().lines(|r| {
-        r.list(",", |r| r.unsigned())
-    })?;
+        for (a, b) in rules {
+            rule_map.entry(a).or_insert(HashSet::new()).insert(b);
+        }
+
+        rule_map
+    };
+
+    let _s = phase("check updates");
 
     let mut sum = 0;
 
@@ -55,30 +67,42 @@ pub fn part1(input: String) -> Result<()> {
         }
     }
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
-    let (rules, pages) = input.split_once("\n\n").unwrap();
+pub fn part2(input: String) -> Result<Answer> {
+    let (rules, updates) = {
+        let _s = phase("parse");
 
-    let rules = rules.reader().lines(|r| {
-        let a = r.unsigned()?;
-        r.text("|")?;
-        let b = r.unsigned()?;
-        Ok((a, b))
-    })?;
+        let [mut rules, mut updates] = input.reader().sections(["rules", "updates"])?;
 
-    let mut rule_map = HashMap::new();
+        let rules = rules.lines(|r| {
+            let a = r.unsigned()?;
+            r.text("|")?;
+            let b = r.unsigned()?;
+            Ok((a, b))
+        })?;
 
-    for (a, b) in rules {
-        rule_map.entry(a).or_insert(HashSet::new()).insert(b);
-    }
+        let updates = updates.lines(|r| {
+            r.list(",", |r| r.unsigned())
+        })?;
+
+        (rules, updates)
+    };
+
+    let rule_map = {
+        let _s = phase("rule map");
 
-    let updates = pages.reader().lines(|r| {
-        r.list(",", |r| r.unsigned())
-    })?;
+        let mut rule_map = HashMap::new();
+
+        for (a, b) in rules {
+            rule_map.entry(a).or_insert(HashSet::new()).insert(b);
+        }
+
+        rule_map
+    };
+
+    let _s = phase("check updates");
 
     let mut sum = 0;
 
@@ -104,7 +128,5 @@ pub fn part2(input: String) -> Result<()> {
         }
     }
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
\ No newline at end of file
@@ -1,9 +1,9 @@
-use std::{collections::{HashMap, HashSet}, time::Instant};
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use crate::util::{CellIndex, Grid, IntoGrid};
+use crate::{runner::{Answer, Color, Frame, Frames, param, phase, progress}, util::{CellIndex, Grid, InputError, IntoGrid, Rng}};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
     enum Dir {
@@ -36,11 +36,27 @@ use crate::util::{CellIndex, Grid, IntoGrid};
         fn in_front_of(self, index: CellIndex) -> CellIndex {
             index + self.to_offset()
         }
+
+        fn arrow(self) -> char {
+            match self {
+                Dir::Up => '^',
+                Dir::Down => 'v',
+                Dir::Left => '<',
+                Dir::Right => '>',
+            }
+        }
     }
 
-pub fn part1(input: String) -> Result<()> {
+fn find_guard(grid: &Grid<char>) -> Result<CellIndex> {
+    grid.iter()
+        .find(|(_, ch)| **ch == '^')
+        .map(|(i, _)| i)
+        .ok_or_else(|| InputError::new("the guard '^' somewhere in the map").into())
+}
+
+pub fn part1(input: String) -> Result<Answer> {
     let mut grid = input.grid()?;
-    let mut guard = grid.iter().find(|(_, ch)| **ch == '^').unwrap().0;
+    let mut guard = find_guard(&grid)?;
     let mut dir = Dir::Up;
 
     'outer: while grid.contains(guard) {
@@ -66,13 +82,11 @@ pub fn part1(input: String) -> Result<()> {
 
     let count = grid.cells().iter().filter(|ch| **ch == 'X').count();
 
-    println!("{count}");
-
-    Ok(())
+    Ok(count.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
-    fn is_infinite_loop(grid: &Grid<char>, extra: CellIndex, mut guard: CellIndex) -> Result<bool> {
+pub fn part2(input: String) -> Result<Answer> {
+    fn is_infinite_loop(grid: &Grid<char>, extra: CellIndex, mut guard: CellIndex, max_turns: usize) -> bool {
         let mut visits: HashMap<CellIndex, HashSet<Dir>> = Default::default();
 
         let mut dir = Dir::Up;
@@ -86,8 +100,8 @@ pub fn part2(input: String) -> Result<()> {
     
             loop {
                 i += 1;
-                if i > 5 {
-                    return Ok(true);
+                if i > max_turns {
+                    return true;
                 }
 
                 next = dir.in_front_of(guard);
@@ -109,30 +123,134 @@ pub fn part2(input: String) -> Result<()> {
             guard = next;
 
             if visits.entry(guard).or_default().contains(&dir) {
-                return Ok(true);
+                return true;
             }
         }
 
-        Ok(false)
+        false
     }
 
-    let grid = input.grid()?;
-    let guard = grid.iter().find(|(_, ch)| **ch == '^').unwrap().0;
+    let (grid, guard) = {
+        let _s = phase("parse");
+
+        let grid = input.grid()?;
+        let guard = find_guard(&grid)?;
+
+        (grid, guard)
+    };
+
+    let max_turns = param("max_turns", 5)?;
+
+    let _s = phase("search");
 
-    let start = Instant::now();
+    let indexes = Vec::from_iter(CellIndex::all_indexes_for(&grid).filter(|cell| cell != &guard));
 
-    let indexes = Vec::from_iter(CellIndex::all_indexes_for(&grid));
+    let progress = progress();
+    progress.set_total(indexes.len());
+
+    log::debug!("searching {} cells", indexes.len());
 
     let count = indexes.into_par_iter()
-        .filter(|cell| cell != &guard)
         .filter(|cell| {
-            is_infinite_loop(&grid, *cell, guard).unwrap()
+            let looped = is_infinite_loop(&grid, *cell, guard, max_turns);
+            progress.inc(1);
+            looped
         })
         .count();
 
-    let end = Instant::now();
+    Ok(count.into())
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let width = rng.range(1..11);
+    let height = rng.range(1..11);
+    let guard = rng.range(0..width * height);
+
+    let mut i = 0;
+
+    rng.grid(width, height, |rng| {
+        i += 1;
+        match i - 1 == guard {
+            true => '^',
+            false if rng.chance(0.15) => '#',
+            false => '.',
+        }
+    })
+}
+
+// places every obstruction and walks until the guard leaves or repeats a state
+pub fn part2_oracle(input: String) -> Result<Answer> {
+    let grid = input.grid()?;
+    let start = find_guard(&grid)?;
+
+    let mut count = 0;
+
+    for extra in CellIndex::all_indexes_for(&grid) {
+        if extra == start {
+            continue;
+        }
+
+        let mut seen = HashSet::new();
+        let (mut guard, mut dir) = (start, Dir::Up);
+
+        while seen.insert((guard, dir)) {
+            let next = dir.in_front_of(guard);
+
+            match grid.get(next) {
+                None => break,
+                Some('#') => dir.rotate_right(),
+                Some(_) if next == extra => dir.rotate_right(),
+                Some(_) => guard = next,
+            }
+        }
+
+        if grid.contains(dir.in_front_of(guard)) {
+            count += 1;
+        }
+    }
+
+    Ok(count.into())
+}
+
+// the guard's walk out of the map, a frame every `steps_per_frame` steps
+pub fn visualize(input: String, frames: &mut Frames) -> Result<()> {
+    let mut grid = input.grid()?;
+    let mut guard = find_guard(&grid)?;
+    let mut dir = Dir::Up;
 
-    println!("{count} in {} seconds", (end - start).as_secs());
+    let steps_per_frame = param("steps_per_frame", 1usize)?.max(1);
+    let mut visited = HashSet::new();
+    let mut states = HashSet::new();
+
+    for step in 0.. {
+        grid.set(guard, 'X')?;
+        visited.insert(guard);
+
+        // a guard walking in circles would otherwise draw frames forever
+        let looping = !states.insert((guard, dir));
+
+        let next = dir.in_front_of(guard);
+        let leaving = !grid.contains(next);
+
+        if step % steps_per_frame == 0 || leaving || looping {
+            let frame = Frame::new(grid.clone())
+                .paint(visited.iter().copied(), Color::Yellow)
+                .mark(guard, dir.arrow(), Color::Red)
+                .caption(format!("step {step}, {} cells visited", visited.len()));
+
+            frames.push(frame)?;
+        }
+
+        if looping {
+            anyhow::bail!("the guard walks in a loop after {step} steps and never leaves the map");
+        }
+
+        match grid.get(next) {
+            None => break,
+            Some('#') => dir.rotate_right(),
+            Some(_) => guard = next,
+        }
+    }
 
     Ok(())
 }
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use crate::util::IntoReader;
+use crate::{runner::Answer, util::{IntoReader, Rng}};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Op {
@@ -36,7 +36,7 @@ fn reduce(values: &[usize], ops: &[Op]) -> usize {
 
 // returns true if overflow
 fn inc(ops: &mut [Op], part1: bool) -> bool {
-    if ops.len() == 0 { return true; }
+    if ops.is_empty() { return true; }
 
     let next = if part1 {
         match ops[0] {
@@ -67,17 +67,24 @@ fn inc(ops: &mut [Op], part1: bool) -> bool {
     false
 }
 
-fn run(input: String, part1: bool) -> Result<()> {
-    let mut r = input.reader();
-
-    let tests = r.lines(|r| {
+fn parse(input: &str) -> Result<Vec<(usize, Vec<usize>)>> {
+    input.reader().lines(|r| {
         let result = r.unsigned()?;
         r.text(":")?;
         let values = r.while_ok(|r| {
             r.unsigned()
         });
+
+        if values.is_empty() {
+            return Err(r.error("values after the ':'").into());
+        }
+
         Ok((result, values))
-    })?;
+    })
+}
+
+fn run(input: String, part1: bool) -> Result<Answer> {
+    let tests = parse(&input)?;
 
     let sum: usize = tests
         .into_par_iter()
@@ -102,15 +109,80 @@ fn run(input: String, part1: bool) -> Result<()> {
         .map(|(result, _)| result)
         .sum();
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     run(input, true)
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     run(input, false)
 }
+
+// works backwards from the result, only trying ops that can undo the last value
+fn solvable(result: usize, values: &[usize], part1: bool) -> bool {
+    let Some((&last, rest)) = values.split_last()
+    else { return false };
+
+    if rest.is_empty() {
+        return result == last;
+    }
+
+    if result >= last && solvable(result - last, rest, part1) {
+        return true;
+    }
+
+    if last != 0 && result.is_multiple_of(last) && solvable(result / last, rest, part1) {
+        return true;
+    }
+
+    if !part1 {
+        // a shift that overflows is wider than any result, so it can't be a concatenation
+        let shift = 10usize.checked_pow(last.checked_ilog10().unwrap_or(0) + 1);
+        if shift.is_some_and(|shift| result % shift == last && solvable(result / shift, rest, part1)) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn run_fast(input: String, part1: bool) -> Result<Answer> {
+    let sum: usize = parse(&input)?
+        .into_iter()
+        .filter(|(result, values)| solvable(*result, values, part1))
+        .map(|(result, _)| result)
+        .sum();
+
+    Ok(sum.into())
+}
+
+pub fn part1_fast(input: String) -> Result<Answer> {
+    run_fast(input, true)
+}
+
+pub fn part2_fast(input: String) -> Result<Answer> {
+    run_fast(input, false)
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let mut input = String::new();
+
+    for _ in 0..rng.range(1..7) {
+        let values: Vec<usize> = (0..rng.range(1..6)).map(|_| rng.range(1..21)).collect();
+
+        // mostly solvable equations, otherwise every answer would be zero
+        let result = match rng.chance(0.7) {
+            true => values[1..].iter().fold(values[0], |acc, &value| {
+                rng.pick(&[Op::Add, Op::Mul, Op::Or]).apply(acc, value)
+            }),
+            false => rng.range(1..1000),
+        };
+
+        let values: Vec<String> = values.iter().map(usize::to_string).collect();
+        input.push_str(&format!("{result}: {}\n", values.join(" ")));
+    }
+
+    input
+}
@@ -2,9 +2,9 @@ use std::collections::HashSet;
 
 use anyhow::Result;
 
-use crate::util::{CellIndex, IntoGrid};
+use crate::{runner::Answer, util::{CellIndex, IntoGrid}};
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let mut freqs = Vec::new();
     freqs.extend('a'..='z');
     freqs.extend('A'..='Z');
@@ -42,12 +42,10 @@ pub fn part1(input: String) -> Result<()> {
         }
     }
 
-    println!("{}", antennas.len());
-
-    Ok(())
+    Ok(antennas.len().into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let mut freqs = Vec::new();
     freqs.extend('a'..='z');
     freqs.extend('A'..='Z');
@@ -91,7 +89,5 @@ pub fn part2(input: String) -> Result<()> {
         }
     }
 
-    println!("{}", antennas.len());
-
-    Ok(())
+    Ok(antennas.len().into())
 }
\ No newline at end of file
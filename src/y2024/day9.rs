@@ -1,6 +1,6 @@
 use anyhow::Result;
 
-use crate::util::IntoReader;
+use crate::{runner::Answer, util::{IntoReader, Rng}};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     enum Entry {
@@ -8,7 +8,7 @@ use crate::util::IntoReader;
         File(u32),
     }
 
-pub fn part1(input: String) -> Result<()> {
+pub fn part1(input: String) -> Result<Answer> {
     let mut r = input.reader();
 
     let mut entries = Vec::new();
@@ -30,8 +30,14 @@ pub fn part1(input: String) -> Result<()> {
         Ok(())
     });
 
+    if i == 0 {
+        return Err(r.error("a disk map of digits").into());
+    }
+
+    r.end()?;
+
     let mut i = 0;
-    let mut j = entries.len() - 1;
+    let mut j = entries.len().saturating_sub(1);
 
     loop {
         while i < j {
@@ -66,12 +72,10 @@ pub fn part1(input: String) -> Result<()> {
         })
         .sum();
 
-    println!("{sum}");
-
-    Ok(())
+    Ok(sum.into())
 }
 
-pub fn part2(input: String) -> Result<()> {
+pub fn part2(input: String) -> Result<Answer> {
     let mut r = input.reader();
 
     let mut entries = Vec::new();
@@ -90,7 +94,13 @@ pub fn part2(input: String) -> Result<()> {
         Ok(())
     });
 
-    let mut file_index = entries.len() - 1;
+    if i == 0 {
+        return Err(r.error("a disk map of digits").into());
+    }
+
+    r.end()?;
+
+    let mut file_index = entries.len().saturating_sub(1);
     loop {
         if entries[file_index].0 == Entry::Empty { file_index -= 1; continue; }
 
@@ -133,7 +143,55 @@ pub fn part2(input: String) -> Result<()> {
         }
     }
 
-    println!("{sum}");
+    Ok(sum.into())
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let len = rng.range(1..16);
+
+    // like real inputs, files are never empty but free space can be
+    (0..len)
+        .map(|i| char::from_digit(rng.range(usize::from(i % 2 == 0)..10) as u32, 10).unwrap())
+        .collect::<String>()
+        + "\n"
+}
+
+// moves whole files block by block on the expanded disk
+pub fn part2_oracle(input: String) -> Result<Answer> {
+    let mut blocks: Vec<Option<usize>> = Vec::new();
+
+    for (i, ch) in input.trim().chars().enumerate() {
+        let size = ch.to_digit(10).ok_or_else(|| anyhow::anyhow!("'{ch}' is not a digit"))?;
+        let entry = if i % 2 == 0 { Some(i / 2) } else { None };
+        blocks.extend(std::iter::repeat_n(entry, size as usize));
+    }
+
+    let files = input.trim().len().div_ceil(2);
 
-    Ok(())
-}
\ No newline at end of file
+    for id in (0..files).rev() {
+        let Some(start) = blocks.iter().position(|b| *b == Some(id))
+        else { continue };
+
+        let size = blocks[start..].iter().take_while(|b| **b == Some(id)).count();
+
+        let mut run = 0;
+
+        for i in 0..start {
+            run = if blocks[i].is_none() { run + 1 } else { 0 };
+
+            if run == size {
+                blocks[i + 1 - size..=i].fill(Some(id));
+                blocks[start..start + size].fill(None);
+                break;
+            }
+        }
+    }
+
+    let sum: usize = blocks
+        .iter()
+        .enumerate()
+        .map(|(i, block)| i * block.unwrap_or(0))
+        .sum();
+
+    Ok(sum.into())
+}